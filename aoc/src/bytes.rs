@@ -0,0 +1,153 @@
+//! Parsing numbers straight out of the bytes of an input, without making a `String`, or even checking for UTF-8,
+//! for each line.
+
+use std::{fs::File, io, path::Path};
+
+use memmap2::Mmap;
+
+/// Map the file at `path` into memory, so that a huge input can be parsed without reading it all in first.
+///
+/// # Safety
+///
+/// Nothing else can change the file while it's mapped. If it's written to, the bytes can change under anything
+/// that's reading them, and if it's truncated then reading past the new end kills the process with `SIGBUS`. Nothing
+/// here can stop another process doing either, so it's up to the caller to only map files that are left alone.
+pub unsafe fn map(path: &Path) -> io::Result<Mmap> {
+    let file = File::open(path)?;
+    // SAFETY: passed on to our caller, see above
+    unsafe { Mmap::map(&file) }
+}
+
+/// The lines of `input` along with their 1-based line numbers, without the `\n` or `\r\n` on the end.
+///
+/// The same lines as [`str::lines`], so there's no empty line after a trailing newline.
+pub fn lines(input: &[u8]) -> Lines<'_> {
+    Lines { rest: input, line: 0 }
+}
+
+pub struct Lines<'a> {
+    rest: &'a [u8],
+    line: usize,
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = (usize, &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rest.is_empty() {
+            return None;
+        }
+        let (line, rest) = match self.rest.iter().position(|&b| b == b'\n') {
+            Some(end) => (&self.rest[..end], &self.rest[end + 1..]),
+            None => (self.rest, &self.rest[self.rest.len()..]),
+        };
+        self.rest = rest;
+        self.line += 1;
+        Some((self.line, line.strip_suffix(b"\r").unwrap_or(line)))
+    }
+}
+
+/// A number that can be parsed from base 10 digits.
+pub trait Decimal: Sized {
+    /// Parse the whole of `bytes` as a number, with an optional `+` in front, or `-` if it's signed. Anything else in
+    /// there, including whitespace, or a number too big for `Self`, gives `None`: the same as [`str::parse`].
+    fn parse_decimal(bytes: &[u8]) -> Option<Self>;
+}
+
+macro_rules! unsigned_decimal {
+    ($($t:ty),*) => {$(
+        impl Decimal for $t {
+            fn parse_decimal(bytes: &[u8]) -> Option<Self> {
+                let digits = bytes.strip_prefix(b"+").unwrap_or(bytes);
+                if digits.is_empty() {
+                    return None;
+                }
+                digits.iter().try_fold(0 as $t, |n, &b| {
+                    let digit = b.wrapping_sub(b'0');
+                    if digit > 9 {
+                        return None;
+                    }
+                    n.checked_mul(10)?.checked_add(digit as $t)
+                })
+            }
+        }
+    )*};
+}
+
+macro_rules! signed_decimal {
+    ($($t:ty),*) => {$(
+        impl Decimal for $t {
+            fn parse_decimal(bytes: &[u8]) -> Option<Self> {
+                let (negative, digits) = match bytes {
+                    [b'-', digits @ ..] => (true, digits),
+                    [b'+', digits @ ..] => (false, digits),
+                    digits => (false, digits),
+                };
+                if digits.is_empty() {
+                    return None;
+                }
+                // negatives are added up as negatives, so that `MIN` doesn't overflow on the way there
+                digits.iter().try_fold(0 as $t, |n, &b| {
+                    let digit = b.wrapping_sub(b'0');
+                    if digit > 9 {
+                        return None;
+                    }
+                    let n = n.checked_mul(10)?;
+                    if negative { n.checked_sub(digit as $t) } else { n.checked_add(digit as $t) }
+                })
+            }
+        }
+    )*};
+}
+
+unsigned_decimal!(u8, u16, u32, u64, usize);
+signed_decimal!(i8, i16, i32, i64, isize);
+
+/// Parse the whole of `bytes` as a binary number of at most 32 bits.
+pub fn binary(bytes: &[u8]) -> Option<u32> {
+    if bytes.is_empty() || bytes.len() > 32 {
+        return None;
+    }
+    bytes.iter().try_fold(0, |n, &b| match b {
+        b'0' => Some(n << 1),
+        b'1' => Some(n << 1 | 1),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn lines_match_str_lines() {
+        for input in ["", "\n", "a", "a\n", "a\r\nb", "a\n\nb\n", "\r\n\r\n"] {
+            let expected: Vec<&str> = input.lines().collect();
+            let actual: Vec<&[u8]> = lines(input.as_bytes()).map(|(_, line)| line).collect();
+            assert_eq!(actual, expected.iter().map(|l| l.as_bytes()).collect::<Vec<_>>(), "{:?}", input);
+        }
+        let numbers: Vec<usize> = lines(b"a\nb\nc").map(|(n, _)| n).collect();
+        assert_eq!(numbers, [1, 2, 3]);
+    }
+
+    #[test]
+    fn decimals_match_from_str() {
+        for s in ["0", "7", "0042", "4294967295", "4294967296", "-1", "+1", "", "-", "1 ", " 1", "1a", "\u{661}"] {
+            assert_eq!(u32::parse_decimal(s.as_bytes()), s.parse().ok(), "u32 {:?}", s);
+            assert_eq!(i32::parse_decimal(s.as_bytes()), s.parse().ok(), "i32 {:?}", s);
+        }
+        for n in [i64::MIN, i64::MIN + 1, -1, 0, i64::MAX] {
+            assert_eq!(i64::parse_decimal(n.to_string().as_bytes()), Some(n));
+        }
+        assert_eq!(i64::parse_decimal(b"-9223372036854775809"), None);
+        assert_eq!(u64::parse_decimal(u64::MAX.to_string().as_bytes()), Some(u64::MAX));
+    }
+
+    #[test]
+    fn binaries_match_from_str_radix() {
+        for s in ["0", "1", "10110", "00100", "11111111111111111111111111111111", "", "2", "1 0", "-1"] {
+            assert_eq!(binary(s.as_bytes()), u32::from_str_radix(s, 2).ok(), "{:?}", s);
+        }
+        assert_eq!(binary(&[b'1'; 33]), None);
+    }
+}
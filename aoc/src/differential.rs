@@ -0,0 +1,51 @@
+use std::fmt::Debug;
+
+use proptest::{
+    prop_assert, prop_assert_eq,
+    strategy::Strategy,
+    test_runner::{Config, TestError, TestRunner},
+};
+
+use crate::{solve, Part, Solution};
+
+/// Check that `Reference` and `Optimised` give the same answers for every input that `inputs` comes up with, or both
+/// reject it. When they disagree, the input is shrunk down to the smallest one that still shows the problem.
+///
+/// `part` picks which parts to compare, for references that are too slow to do both.
+pub fn assert_agree<Reference: Solution, Optimised: Solution>(part: Option<Part>, inputs: impl Strategy<Value = String>) {
+    // there's no source file to save failures next to, so don't try
+    let config = Config { failure_persistence: None, ..Config::default() };
+    assert_agree_with::<Reference, Optimised>(config, part, inputs)
+}
+
+/// [`assert_agree`], with control over how many cases are tried and so on.
+pub fn assert_agree_with<Reference: Solution, Optimised: Solution>(
+    config: Config,
+    part: Option<Part>,
+    inputs: impl Strategy<Value = String>,
+) {
+    let mut runner = TestRunner::new(config);
+    let result = runner.run(&inputs, |input| {
+        match (solve::<Reference>(&input, part), solve::<Optimised>(&input, part)) {
+            (Ok(reference), Ok(optimised)) => prop_assert_eq!(reference, optimised),
+            (Err(_), Err(_)) => (),
+            (reference, optimised) => prop_assert!(
+                false,
+                "reference gave {:?} but optimised gave {:?}",
+                reference,
+                optimised,
+            ),
+        }
+        Ok(())
+    });
+    match result {
+        Ok(()) => (),
+        Err(TestError::Fail(reason, input)) => panic!("{}\nminimal failing input:\n{}", reason, input),
+        Err(e @ TestError::Abort(_)) => panic!("{}", e),
+    }
+}
+
+/// Render each generated value as a puzzle input, one line per item.
+pub fn lines<T: Debug>(items: impl Strategy<Value = Vec<T>>, line: fn(&T) -> String) -> impl Strategy<Value = String> {
+    items.prop_map(move |items| items.iter().map(|item| line(item) + "\n").collect())
+}
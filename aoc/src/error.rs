@@ -0,0 +1,69 @@
+use std::fmt::{self, Display};
+
+/// Something wrong with a puzzle input, and where it was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    pub day: u8,
+    /// The 1-based line of the input the problem was found on, if it can be pinned down to one.
+    pub line: Option<usize>,
+    pub cause: Cause,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Cause {
+    /// There was nothing to parse.
+    EmptyInput,
+    /// A line didn't look like what we were expecting.
+    Malformed { content: String, expected: &'static str },
+    /// A submarine command that isn't `forward`, `down` or `up`.
+    UnknownCommand(String),
+    /// The input is valid but uses a shape that this solution can't handle.
+    Unsupported(String),
+    /// Every candidate got filtered out before a single answer was left.
+    NoCandidates,
+    /// We ran out of ways to filter the candidates but more than one is left.
+    Ambiguous,
+    /// Working something out, named here, got too big for the type it's worked out in.
+    Overflow(&'static str),
+}
+
+impl Error {
+    pub fn new(day: u8, cause: Cause) -> Self {
+        Error { day, line: None, cause }
+    }
+
+    pub fn at_line(day: u8, line: usize, cause: Cause) -> Self {
+        Error { day, line: Some(line), cause }
+    }
+
+    /// A [`Cause::Malformed`] error for `content` on `line`.
+    pub fn malformed(day: u8, line: usize, content: &str, expected: &'static str) -> Self {
+        Self::at_line(day, line, Cause::Malformed { content: content.to_string(), expected })
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "day {}", self.day)?;
+        if let Some(line) = self.line {
+            write!(f, ", line {}", line)?;
+        }
+        write!(f, ": {}", self.cause)
+    }
+}
+
+impl Display for Cause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Cause::EmptyInput => write!(f, "the input is empty"),
+            Cause::Malformed { content, expected } => write!(f, "expected {}, found {:?}", expected, content),
+            Cause::UnknownCommand(command) => write!(f, "unknown command {:?}", command),
+            Cause::Unsupported(reason) => write!(f, "unsupported input: {}", reason),
+            Cause::NoCandidates => write!(f, "every candidate was filtered out"),
+            Cause::Ambiguous => write!(f, "more than one candidate is left"),
+            Cause::Overflow(what) => write!(f, "{} got too big", what),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
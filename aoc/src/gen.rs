@@ -0,0 +1,23 @@
+use rand::{rngs::StdRng, RngCore, SeedableRng};
+
+use crate::Error;
+
+/// How big and what shape a generated input should be. Anything left as `None` gets that day's default, and days
+/// ignore whichever fields don't make sense for them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Shape {
+    /// Roughly how many lines of input, or for a polymer the length of the template.
+    pub size: Option<usize>,
+    /// How many bits wide each diagnostic reading is.
+    pub width: Option<usize>,
+    /// Which characters a polymer can be built from.
+    pub alphabet: Option<String>,
+}
+
+/// Writes a random but valid puzzle input.
+pub type Generator = fn(&mut dyn RngCore, &Shape) -> Result<String, Error>;
+
+/// Run `generator` from a fixed `seed`, so the same seed always gives the same input.
+pub fn generate(generator: Generator, seed: u64, shape: &Shape) -> Result<String, Error> {
+    generator(&mut StdRng::seed_from_u64(seed), shape)
+}
@@ -0,0 +1,114 @@
+use std::{fmt::Display, hint::black_box, time::{Duration, Instant}};
+
+pub mod bytes;
+#[cfg(feature = "differential")]
+pub mod differential;
+mod error;
+pub mod gen;
+pub mod stream;
+pub mod window;
+pub use error::{Cause, Error};
+pub use stream::{WindowMapStream, WindowMapStreamExt};
+pub use window::{SliceWindowMap, Trailing, WindowMap, WindowMapExt, WindowMapStep};
+
+/// A single day's puzzle: parse the input once, then answer both parts from the parsed form.
+///
+/// Parsing is kept separate from solving so that the two parts can share the (sometimes expensive) parsed
+/// representation, and so that callers can time or test each stage on its own.
+pub trait Solution {
+    /// Which day of the calendar this solves, used to label errors.
+    const DAY: u8;
+    /// The parsed puzzle input, shared by both parts.
+    type Input;
+    type Part1: Display;
+    type Part2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>;
+    fn part_1(input: &Self::Input) -> Result<Self::Part1, Error>;
+    fn part_2(input: &Self::Input) -> Result<Self::Part2, Error>;
+}
+
+/// Stand-in answer for a part that hasn't been solved yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unsolved;
+
+impl Display for Unsolved {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unsolved")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+/// The rendered answers from a run; parts that weren't asked for are `None`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(part_1) = &self.part_1 {
+            writeln!(f, "part 1: {}", part_1)?;
+        }
+        if let Some(part_2) = &self.part_2 {
+            writeln!(f, "part 2: {}", part_2)?;
+        }
+        Ok(())
+    }
+}
+
+/// A type-erased [`solve`], so that every day can sit behind the same function pointer.
+pub type Solver = fn(&str, Option<Part>) -> Result<Answers, Error>;
+
+/// Parse `input` and solve the requested part, or both if `part` is `None`.
+pub fn solve<S: Solution>(input: &str, part: Option<Part>) -> Result<Answers, Error> {
+    let input = S::parse(input)?;
+    let mut answers = Answers::default();
+    if part != Some(Part::Two) {
+        answers.part_1 = Some(S::part_1(&input)?.to_string());
+    }
+    if part != Some(Part::One) {
+        answers.part_2 = Some(S::part_2(&input)?.to_string());
+    }
+    Ok(answers)
+}
+
+/// How long each stage of a single run took.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part_1: Duration,
+    pub part_2: Duration,
+}
+
+/// A type-erased [`time`], the timing counterpart to [`Solver`].
+pub type Timer = fn(&str) -> Result<Timings, Error>;
+
+/// Parse `input` and solve both parts once, timing each stage separately.
+pub fn time<S: Solution>(input: &str) -> Result<Timings, Error> {
+    let start = Instant::now();
+    let input = S::parse(black_box(input))?;
+    let parse = start.elapsed();
+
+    let start = Instant::now();
+    black_box(S::part_1(&input)?);
+    let part_1 = start.elapsed();
+
+    let start = Instant::now();
+    black_box(S::part_2(&input)?);
+    let part_2 = start.elapsed();
+
+    Ok(Timings { parse, part_1, part_2 })
+}
+
+/// Parse `input` and print the answers to both parts.
+pub fn run<S: Solution>(input: &str) -> Result<(), Error> {
+    print!("{}", solve::<S>(input, None)?);
+    Ok(())
+}
@@ -0,0 +1,150 @@
+//! [`window_map`](crate::WindowMapExt::window_map) for async streams, for when the items come in over a channel
+//! rather than all being there up front.
+
+use std::{
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+
+use futures_core::{FusedStream, Stream};
+
+use crate::window::{ring_window, windows_left};
+
+pub trait WindowMapStreamExt: Stream + Sized {
+    /// Call `f` on every run of `N` consecutive items as soon as the last of them arrives, the same windows as
+    /// [`WindowMapExt::window_map`](crate::WindowMapExt::window_map) would give. The items are moved into a ring
+    /// buffer and lent to `f`, never cloned.
+    ///
+    /// The stream has to be [`Unpin`], so pin anything that isn't with [`Box::pin`] or [`std::pin::pin!`] first.
+    ///
+    /// # Panics
+    ///
+    /// If `N` is 0.
+    fn window_map<B, F, const N: usize>(self, f: F) -> WindowMapStream<B, Self, F, N>
+        where F: FnMut([&Self::Item; N]) -> B
+    {
+        assert!(N > 0, "a window has to have at least one item in it");
+        WindowMapStream { f, s: self, filling: Vec::with_capacity(N), ring: None, done: false }
+    }
+}
+impl<S: Stream> WindowMapStreamExt for S {}
+
+pub struct WindowMapStream<B, S: Stream, F: FnMut([&S::Item; N]) -> B, const N: usize> {
+    f: F,
+    s: S,
+    /// The items so far while there aren't enough for the first window yet, which can take more than one poll.
+    filling: Vec<S::Item>,
+    /// The last window, as a ring buffer and the index of its first item.
+    ring: Option<([S::Item; N], usize)>,
+    /// Whether `s` has given us `None`. Streams can panic if they're polled again after that, so we don't.
+    done: bool,
+}
+
+// Only `s` is ever polled through a pin, everything else is just moved about, so we're as `Unpin` as it is
+impl<B, S: Stream + Unpin, F: FnMut([&S::Item; N]) -> B, const N: usize> Unpin for WindowMapStream<B, S, F, N> {}
+
+impl<B, S: Stream + Unpin, F: FnMut([&S::Item; N]) -> B, const N: usize> Stream for WindowMapStream<B, S, F, N> {
+    type Item = B;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.done {
+            return Poll::Ready(None);
+        }
+        loop {
+            let Some(item) = ready!(Pin::new(&mut this.s).poll_next(cx)) else {
+                this.done = true;
+                return Poll::Ready(None);
+            };
+            match &mut this.ring {
+                Some((buffer, head)) => {
+                    // drop the oldest item, advance the ring buffer
+                    buffer[*head] = item;
+                    *head = (*head + 1) % N;
+                    return Poll::Ready(Some((this.f)(ring_window(buffer, *head))));
+                }
+                None => {
+                    this.filling.push(item);
+                    if this.filling.len() == N {
+                        let buffer: [S::Item; N] = std::mem::take(&mut this.filling).try_into()
+                            .unwrap_or_else(|_| unreachable!("there are exactly N items"));
+                        let (buffer, head) = this.ring.insert((buffer, 0));
+                        return Poll::Ready(Some((this.f)(ring_window(buffer, *head))));
+                    }
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+        // the items we've got that can still be in a window, which for a full ring is all but the oldest
+        let live = if self.ring.is_some() { N - 1 } else { self.filling.len() };
+        windows_left::<N>(live, self.s.size_hint())
+    }
+}
+
+impl<B, S: Stream + Unpin, F: FnMut([&S::Item; N]) -> B, const N: usize> FusedStream for WindowMapStream<B, S, F, N> {
+    fn is_terminated(&self) -> bool {
+        self.done
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::WindowMapExt;
+    use futures::{executor::block_on, stream, StreamExt};
+
+    /// Returns `Pending` before every item, the way a channel does when the sender hasn't caught up.
+    struct Slow<S> {
+        s: S,
+        ready: bool,
+    }
+
+    impl<S: Stream + Unpin> Stream for Slow<S> {
+        type Item = S::Item;
+
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            self.ready = !self.ready;
+            if self.ready {
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            Pin::new(&mut self.s).poll_next(cx)
+        }
+    }
+
+    #[test]
+    fn matches_window_map() {
+        fn check<const N: usize>(items: &[u32]) {
+            let sum = |window: [&u32; N]| window.into_iter().sum::<u32>();
+            let expected: Vec<u32> = items.iter().copied().window_map(sum).collect();
+            let windows = stream::iter(items.iter().copied()).window_map(sum);
+            assert_eq!(windows.size_hint(), (expected.len(), Some(expected.len())), "N = {}, {:?}", N, items);
+            assert_eq!(block_on(windows.collect::<Vec<_>>()), expected, "N = {}, {:?}", N, items);
+            let slow = Slow { s: stream::iter(items.iter().copied()), ready: false };
+            assert_eq!(block_on(slow.window_map(sum).collect::<Vec<_>>()), expected, "N = {}, {:?}", N, items);
+        }
+        for len in 0..8 {
+            let items: Vec<u32> = (1..=len).collect();
+            check::<1>(&items);
+            check::<2>(&items);
+            check::<3>(&items);
+            check::<5>(&items);
+        }
+    }
+
+    #[test]
+    fn items_that_cant_be_cloned() {
+        struct Reading(u32);
+        let readings = || stream::iter([1, 2, 3, 4].map(Reading));
+        let windows = readings().window_map(|[a, b]| a.0 + b.0);
+        assert_eq!(block_on(windows.collect::<Vec<_>>()), [3, 5, 7]);
+        let mut windows = readings().window_map(|[_, _, _, _, _]| ());
+        assert_eq!(block_on(windows.next()), None);
+        assert!(windows.is_terminated());
+    }
+}
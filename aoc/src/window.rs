@@ -0,0 +1,524 @@
+//! Sliding windows over any iterator, lent to a closure rather than cloned out.
+
+use std::{collections::VecDeque, iter::FusedIterator};
+
+#[cfg(feature = "nightly")]
+use itertools::Itertools;
+
+pub trait WindowMapExt: Iterator + Sized {
+    /// Call `f` on every run of `N` consecutive items, like [`slice::windows`] but for any iterator.
+    ///
+    /// If there are fewer than `N` items there are no windows at all.
+    ///
+    /// # Panics
+    ///
+    /// If `N` is 0.
+    fn window_map<B, F, const N: usize>(self, f: F) -> WindowMap<B, Self, F, N>
+        where F: FnMut([&Self::Item; N]) -> B
+    {
+        assert!(N > 0, "a window has to have at least one item in it");
+        WindowMap { f, i: self, front: None, back: None, rest: None }
+    }
+
+    /// Call `f` on windows of `N` items that start every `S` items, so `S` less than `N` gives overlapping windows
+    /// and `S` more than `N` skips the items in between.
+    ///
+    /// The windows are passed as slices so that `trailing` can decide what happens when the items run out part way
+    /// through a window. That last window is only ever made if it has an item in it that no earlier window had.
+    /// `N` and `S` can't be worked out from a slice so they come first, e.g. `window_map_step::<3, 2, _, _>`.
+    ///
+    /// # Panics
+    ///
+    /// If `N` or `S` is 0.
+    fn window_map_step<const N: usize, const S: usize, B, F>(self, trailing: Trailing<Self::Item>, f: F) -> WindowMapStep<B, Self, F, N, S>
+        where F: FnMut(&[&Self::Item]) -> B
+    {
+        assert!(N > 0, "a window has to have at least one item in it");
+        assert!(S > 0, "windows have to move along by at least one item");
+        WindowMapStep { f, i: self, trailing, buffer: VecDeque::with_capacity(N), fresh: 0, skip: 0, done: false }
+    }
+
+    /// Call `f` on consecutive chunks of `N` items that don't overlap, the same as `window_map_step::<N, N, _, _>`.
+    fn chunk_map<const N: usize, B, F>(self, trailing: Trailing<Self::Item>, f: F) -> WindowMapStep<B, Self, F, N, N>
+        where F: FnMut(&[&Self::Item]) -> B
+    {
+        self.window_map_step::<N, N, B, F>(trailing, f)
+    }
+
+    /// The same windows as [`window_map`](WindowMapExt::window_map), but lent to `f` as one contiguous slice, so
+    /// it can use slice methods like `iter().sum()` and be vectorised.
+    ///
+    /// The items are kept in a buffer twice as long as a window rather than a ring, so each window is just the
+    /// last `N` items in it. Once it's full the last window is moved down to the start, which is the only
+    /// shuffling that happens and only once every `N` windows. Nothing is cloned.
+    ///
+    /// # Panics
+    ///
+    /// If `N` is 0.
+    fn slice_window_map<B, F, const N: usize>(self, f: F) -> SliceWindowMap<B, Self, F, N>
+        where F: FnMut(&[Self::Item; N]) -> B
+    {
+        assert!(N > 0, "a window has to have at least one item in it");
+        SliceWindowMap { f, i: self, buffer: Vec::with_capacity(2 * N), done: false }
+    }
+}
+impl<I: Iterator> WindowMapExt for I {}
+
+/// What to do with the last few items when there aren't enough of them left to fill a window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trailing<T> {
+    /// Leave them out, so that every window is full.
+    Drop,
+    /// Fill the rest of the window up with references to this.
+    Pad(T),
+    /// Pass a window that's shorter than `N`.
+    Short,
+}
+
+// Okay to avoid the need to return references, or to clone to avoid returning references, just force the user to consume them immediately
+pub struct WindowMap<B, I: Iterator, F: FnMut([&I::Item; N]) -> B, const N: usize> {
+    f: F,
+    i: I,
+    /// The last window taken from the front, as a ring buffer and the index of its first item.
+    front: Option<([I::Item; N], usize)>,
+    /// The last window taken from the back, the same way round as `front`.
+    back: Option<([I::Item; N], usize)>,
+    /// Once `i` has run out: every item that's still in a window that hasn't been taken yet, in order.
+    ///
+    /// `i` is never polled again after this is set, which is what makes us fused.
+    rest: Option<VecDeque<I::Item>>,
+}
+
+impl<B, I: Iterator, F: FnMut([&I::Item; N]) -> B, const N: usize> WindowMap<B, I, F, N> {
+    fn fill_front(&mut self) -> Result<[I::Item; N], Vec<I::Item>> {
+        // itertools drops a partial array on the floor, which is only fine when there's nothing at the back for
+        // the partial array to make windows with
+        #[cfg(feature = "nightly")]
+        if self.back.is_none() {
+            return self.i.next_array().ok_or_else(Vec::new);
+        }
+        take_array(self.i.by_ref().fuse())
+    }
+
+    /// `i` has run out, so gather up everything that's left, with `middle` being whatever was taken from `i`
+    /// without making a whole window.
+    fn finish(&mut self, middle: Vec<I::Item>) {
+        let mut rest = VecDeque::new();
+        if let Some((buffer, head)) = self.front.take() {
+            // the first item's only window has already been taken
+            rest.extend(in_order(buffer, head).skip(1));
+        }
+        rest.extend(middle);
+        if let Some((buffer, head)) = self.back.take() {
+            rest.extend(in_order(buffer, head).take(N - 1));
+        }
+        self.rest = Some(rest);
+    }
+}
+
+impl<B, I: Iterator, F: FnMut([&I::Item; N]) -> B, const N: usize> Iterator for WindowMap<B, I, F, N> {
+    type Item = B;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rest.is_none() {
+            if let Some((buffer, head)) = &mut self.front {
+                match self.i.next() {
+                    Some(item) => {
+                        // drop the oldest item, advance the ring buffer
+                        buffer[*head] = item;
+                        *head = (*head + 1) % N;
+                    }
+                    None => self.finish(vec![]),
+                }
+            } else {
+                // first call so init
+                match self.fill_front() {
+                    Ok(buffer) => self.front = Some((buffer, 0)),
+                    Err(partial) => self.finish(partial),
+                }
+            }
+        }
+
+        match &mut self.rest {
+            None => {
+                let (buffer, head) = self.front.as_ref().expect("we just made sure it was initialised!");
+                Some((self.f)(ring_window(buffer, *head)))
+            }
+            Some(rest) => {
+                if rest.len() < N {
+                    return None;
+                }
+                let output = (self.f)(std::array::from_fn(|k| &rest[k]));
+                rest.pop_front();
+                Some(output)
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if let Some(rest) = &self.rest {
+            let windows = (rest.len() + 1).saturating_sub(N);
+            return (windows, Some(windows));
+        }
+        // The items in the rings that can still be in a window, which is all but the one at the far end of each
+        let live = [&self.front, &self.back].iter().filter(|ring| ring.is_some()).count() * (N - 1);
+        windows_left::<N>(live, self.i.size_hint())
+    }
+}
+
+impl<B, I: DoubleEndedIterator, F: FnMut([&I::Item; N]) -> B, const N: usize> DoubleEndedIterator for WindowMap<B, I, F, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.rest.is_none() {
+            if let Some((buffer, head)) = &mut self.back {
+                match self.i.next_back() {
+                    Some(item) => {
+                        // the same as the front, but the new item goes before the old first item
+                        *head = (*head + N - 1) % N;
+                        buffer[*head] = item;
+                    }
+                    None => self.finish(vec![]),
+                }
+            } else {
+                match take_array(self.i.by_ref().rev().fuse()) {
+                    Ok(mut buffer) => {
+                        buffer.reverse();
+                        self.back = Some((buffer, 0));
+                    }
+                    Err(mut partial) => {
+                        partial.reverse();
+                        self.finish(partial);
+                    }
+                }
+            }
+        }
+
+        match &mut self.rest {
+            None => {
+                let (buffer, head) = self.back.as_ref().expect("we just made sure it was initialised!");
+                Some((self.f)(ring_window(buffer, *head)))
+            }
+            Some(rest) => {
+                if rest.len() < N {
+                    return None;
+                }
+                let start = rest.len() - N;
+                let output = (self.f)(std::array::from_fn(|k| &rest[start + k]));
+                rest.pop_back();
+                Some(output)
+            }
+        }
+    }
+}
+
+impl<B, I: ExactSizeIterator, F: FnMut([&I::Item; N]) -> B, const N: usize> ExactSizeIterator for WindowMap<B, I, F, N> {}
+
+impl<B, I: Iterator, F: FnMut([&I::Item; N]) -> B, const N: usize> FusedIterator for WindowMap<B, I, F, N> {}
+
+pub struct WindowMapStep<B, I: Iterator, F: FnMut(&[&I::Item]) -> B, const N: usize, const S: usize> {
+    f: F,
+    i: I,
+    trailing: Trailing<I::Item>,
+    /// The start of the next window, which is never more than `N` items so it doesn't have to grow.
+    buffer: VecDeque<I::Item>,
+    /// How many items at the end of `buffer` haven't been in a window yet.
+    fresh: usize,
+    /// How many items to throw away before the next window starts, when the windows have gaps between them.
+    skip: usize,
+    /// Set once `i` has run out, after which it's never polled again.
+    done: bool,
+}
+
+impl<B, I: Iterator, F: FnMut(&[&I::Item]) -> B, const N: usize, const S: usize> WindowMapStep<B, I, F, N, S> {
+    /// How many windows are left if `i` has `inner` more items, or `None` if that's too many to count.
+    fn remaining(&self, inner: usize) -> Option<usize> {
+        let inner = inner.saturating_sub(self.skip);
+        let items = self.buffer.len().checked_add(inner)?;
+        let fresh = self.fresh + inner;
+        let full = if items >= N { (items - N) / S + 1 } else { 0 };
+        // the items that are left over after the last full window, and not still in it
+        let leftover = match full {
+            0 => fresh,
+            _ => items.saturating_sub(((full - 1) * S + N).max(full * S)),
+        };
+        Some(full + usize::from(leftover > 0 && !matches!(self.trailing, Trailing::Drop)))
+    }
+}
+
+impl<B, I: Iterator, F: FnMut(&[&I::Item]) -> B, const N: usize, const S: usize> Iterator for WindowMapStep<B, I, F, N, S> {
+    type Item = B;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        while self.skip > 0 {
+            if self.i.next().is_none() {
+                self.done = true;
+                return None;
+            }
+            self.skip -= 1;
+        }
+        while self.buffer.len() < N {
+            match self.i.next() {
+                Some(item) => {
+                    self.buffer.push_back(item);
+                    self.fresh += 1;
+                }
+                None => {
+                    self.done = true;
+                    break;
+                }
+            }
+        }
+
+        let output = if self.buffer.len() == N {
+            let window: [&I::Item; N] = std::array::from_fn(|k| &self.buffer[k]);
+            (self.f)(&window)
+        } else if self.fresh == 0 {
+            // everything left has already been in a window, and that includes there being nothing left at all
+            return None;
+        } else {
+            // can't be bothered with `MaybeUninit` so fill the whole array with the first item then overwrite it
+            let mut window = [&self.buffer[0]; N];
+            match &self.trailing {
+                Trailing::Drop => return None,
+                Trailing::Pad(pad) => {
+                    for (k, item) in window.iter_mut().enumerate() {
+                        *item = self.buffer.get(k).unwrap_or(pad);
+                    }
+                    (self.f)(&window)
+                }
+                Trailing::Short => {
+                    for (item, buffered) in window.iter_mut().zip(&self.buffer) {
+                        *item = buffered;
+                    }
+                    (self.f)(&window[..self.buffer.len()])
+                }
+            }
+        };
+
+        self.buffer.drain(..S.min(self.buffer.len()));
+        self.skip = S.saturating_sub(N);
+        self.fresh = 0;
+        Some(output)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+        let (lower, upper) = self.i.size_hint();
+        (self.remaining(lower).unwrap_or(usize::MAX), upper.and_then(|upper| self.remaining(upper)))
+    }
+}
+
+impl<B, I: Iterator, F: FnMut(&[&I::Item]) -> B, const N: usize, const S: usize> FusedIterator for WindowMapStep<B, I, F, N, S> {}
+
+pub struct SliceWindowMap<B, I: Iterator, F: FnMut(&[I::Item; N]) -> B, const N: usize> {
+    f: F,
+    i: I,
+    /// Up to `2 * N` items, the last `N` of which are the last window. Never reallocated.
+    buffer: Vec<I::Item>,
+    /// Whether `i` has run out. `buffer` is emptied then too, since there'll be no more windows to put it in.
+    done: bool,
+}
+
+impl<B, I: Iterator, F: FnMut(&[I::Item; N]) -> B, const N: usize> Iterator for SliceWindowMap<B, I, F, N> {
+    type Item = B;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        // `with_capacity` can give us more room than we asked for, so go by the length we want it to be
+        if self.buffer.len() == 2 * N {
+            // out of room, so move the last window down to the start to make room for the next `N` items
+            self.buffer.drain(..N);
+        }
+        // the whole of the first window, then one more item for each window after that
+        let wanted = if self.buffer.len() < N { N } else { self.buffer.len() + 1 };
+        while self.buffer.len() < wanted {
+            match self.i.next() {
+                Some(item) => self.buffer.push(item),
+                None => {
+                    self.done = true;
+                    self.buffer.clear();
+                    return None;
+                }
+            }
+        }
+        let window = self.buffer[self.buffer.len() - N..].try_into().expect("the slice is N long");
+        Some((self.f)(window))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+        // the items we've got that can still be in a window, which once there's been a window is all but the oldest
+        let live = if self.buffer.len() >= N { N - 1 } else { self.buffer.len() };
+        windows_left::<N>(live, self.i.size_hint())
+    }
+}
+
+impl<B, I: ExactSizeIterator, F: FnMut(&[I::Item; N]) -> B, const N: usize> ExactSizeIterator for SliceWindowMap<B, I, F, N> {}
+
+impl<B, I: Iterator, F: FnMut(&[I::Item; N]) -> B, const N: usize> FusedIterator for SliceWindowMap<B, I, F, N> {}
+
+/// The next `N` items, or if there aren't that many then however many there were.
+fn take_array<T, const N: usize>(mut items: impl Iterator<Item = T>) -> Result<[T; N], Vec<T>> {
+    let items: [Option<T>; N] = std::array::from_fn(|_| items.next());
+    if items.iter().any(Option::is_none) {
+        return Err(items.into_iter().flatten().collect());
+    }
+    Ok(items.map(|item| item.expect("we just checked they're all there")))
+}
+
+/// How many more windows of `N` items there'll be, from the size hint of the items still to come and the `live` items
+/// we're already holding on to that can still be in a window.
+pub(crate) fn windows_left<const N: usize>(live: usize, (lower, upper): (usize, Option<usize>)) -> (usize, Option<usize>) {
+    let windows = |inner: usize| inner.checked_add(live + 1).map(|items| items.saturating_sub(N));
+    (windows(lower).unwrap_or(usize::MAX), upper.and_then(windows))
+}
+
+pub(crate) fn ring_window<T, const N: usize>(buffer: &[T; N], head: usize) -> [&T; N] {
+    std::array::from_fn(|k| &buffer[(head + k) % N])
+}
+
+fn in_order<T, const N: usize>(buffer: [T; N], head: usize) -> impl Iterator<Item = T> {
+    let mut items = VecDeque::from(Vec::from(buffer));
+    items.rotate_left(head);
+    items.into_iter()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn sums<const N: usize>(items: &[u32]) -> Vec<u32> {
+        items.iter().copied().window_map(|window: [&u32; N]| window.into_iter().sum()).collect()
+    }
+
+    #[test]
+    fn single_item_windows() {
+        assert_eq!(sums::<1>(&[1, 2, 3]), [1, 2, 3]);
+    }
+
+    #[test]
+    fn windows_longer_than_input() {
+        assert_eq!(sums::<4>(&[1, 2, 3]), [0; 0]);
+        assert_eq!(sums::<3>(&[1, 2, 3]), [6]);
+        assert_eq!([1, 2, 3].iter().window_map(|[_, _, _, _]| ()).size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    fn empty_input() {
+        assert_eq!(sums::<1>(&[]), [0; 0]);
+        assert_eq!(sums::<2>(&[]), [0; 0]);
+        let mut windows = [0u32; 0].iter().window_map(|[_, _]| ());
+        assert_eq!(windows.next_back(), None);
+        assert_eq!(windows.next(), None);
+    }
+
+    #[test]
+    fn stepped_windows() {
+        let items = [1, 2, 3, 4, 5, 6, 7];
+        let collect = |w: &[&u32]| w.iter().map(|i| **i).collect::<Vec<_>>();
+        let windows = |trailing| items.into_iter().window_map_step::<3, 2, _, _>(trailing, collect).collect::<Vec<_>>();
+        assert_eq!(windows(Trailing::Drop), [vec![1, 2, 3], vec![3, 4, 5], vec![5, 6, 7]]);
+        // 7 was already in the last full window so there's no short one after it
+        assert_eq!(windows(Trailing::Short), [vec![1, 2, 3], vec![3, 4, 5], vec![5, 6, 7]]);
+
+        let chunks = |trailing| items.into_iter().chunk_map::<3, _, _>(trailing, collect).collect::<Vec<_>>();
+        assert_eq!(chunks(Trailing::Drop), [vec![1, 2, 3], vec![4, 5, 6]]);
+        assert_eq!(chunks(Trailing::Pad(0)), [vec![1, 2, 3], vec![4, 5, 6], vec![7, 0, 0]]);
+        assert_eq!(chunks(Trailing::Short), [vec![1, 2, 3], vec![4, 5, 6], vec![7]]);
+    }
+
+    // Every combination of a few window sizes and steps on short inputs, against slicing the windows out by hand
+    #[test]
+    fn stepped_windows_match_slices() {
+        fn check<const N: usize, const S: usize>() {
+            for len in 0..12 {
+                let items: Vec<usize> = (0..len).collect();
+                let mut expected = vec![];
+                let mut start = 0;
+                while start + N <= len {
+                    expected.push(items[start..start + N].to_vec());
+                    start += S;
+                }
+                // the short window at the end, as long as it has something the last full window didn't
+                let seen = if start == 0 { 0 } else { start - S + N };
+                let short = (start < len && len > seen).then(|| items[start..].to_vec());
+
+                for (trailing, short) in [
+                    (Trailing::Drop, None),
+                    (Trailing::Short, short.clone()),
+                    (Trailing::Pad(99), short.clone().map(|mut w| { w.resize(N, 99); w })),
+                ] {
+                    let windows = items.iter().copied()
+                        .window_map_step::<N, S, _, _>(trailing, |w| w.iter().map(|i| **i).collect::<Vec<_>>());
+                    let expected: Vec<Vec<usize>> = expected.iter().cloned().chain(short).collect();
+                    assert_eq!(windows.size_hint(), (expected.len(), Some(expected.len())), "N = {}, S = {}, len = {}", N, S, len);
+                    assert_eq!(windows.collect::<Vec<_>>(), expected, "N = {}, S = {}, len = {}", N, S, len);
+                }
+            }
+        }
+        check::<1, 1>();
+        check::<1, 3>();
+        check::<2, 1>();
+        check::<3, 2>();
+        check::<3, 3>();
+        check::<2, 5>();
+        check::<4, 3>();
+    }
+
+    // Every way of taking windows from either end of short inputs, against `slice::windows`
+    #[test]
+    fn matches_slice_windows_from_both_ends() {
+        fn check<const N: usize>() {
+            for len in 0..8 {
+                let items: Vec<usize> = (0..len).collect();
+                let expected: Vec<Vec<usize>> = items.windows(N).map(<[usize]>::to_vec).collect();
+                for pattern in 0..1u32 << expected.len().max(1) {
+                    let mut windows = items.iter().copied().window_map(|window: [&usize; N]| window.map(|i| *i).to_vec());
+                    let (mut front, mut back) = (vec![], vec![]);
+                    for step in 0..expected.len() {
+                        assert_eq!(windows.len(), expected.len() - step);
+                        if pattern & 1 << step == 0 {
+                            front.push(windows.next().unwrap());
+                        } else {
+                            back.push(windows.next_back().unwrap());
+                        }
+                    }
+                    assert_eq!(windows.len(), 0);
+                    assert_eq!(windows.next(), None);
+                    assert_eq!(windows.next_back(), None);
+                    front.extend(back.into_iter().rev());
+                    assert_eq!(front, expected, "N = {}, len = {}, pattern = {:b}", N, len, pattern);
+                }
+            }
+        }
+        check::<1>();
+        check::<2>();
+        check::<3>();
+        check::<4>();
+    }
+
+    #[test]
+    fn slice_windows_match_ring_windows() {
+        fn check<const N: usize>() {
+            for len in 0..4 * N + 2 {
+                let items: Vec<String> = (0..len).map(|i| i.to_string()).collect();
+                let expected: Vec<String> = items.iter().cloned().window_map(|window: [&String; N]| window.map(String::as_str).concat()).collect();
+                let windows = items.iter().cloned().slice_window_map(|window: &[String; N]| window.concat());
+                assert_eq!(windows.size_hint(), (expected.len(), Some(expected.len())), "N = {}, len = {}", N, len);
+                assert_eq!(windows.collect::<Vec<_>>(), expected, "N = {}, len = {}", N, len);
+            }
+        }
+        check::<1>();
+        check::<2>();
+        check::<3>();
+        check::<5>();
+    }
+}
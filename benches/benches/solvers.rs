@@ -0,0 +1,163 @@
+use std::{
+    io::{BufRead, BufReader},
+    num::NonZeroUsize,
+};
+
+use aoc::{gen::Shape, Solution, WindowMapExt};
+use criterion::{black_box, criterion_group, criterion_main, measurement::WallTime, BenchmarkGroup, BenchmarkId, Criterion, Throughput};
+use itertools::Itertools;
+
+/// How many times larger than the bundled input the scaled-up inputs are.
+const SCALES: [usize; 3] = [1, 10, 100];
+
+/// d1: the const-generic `window_map` against the `tuple_windows` it replaced, and the runtime-sized rolling sums that
+/// `Day1` actually uses now.
+fn d1_windows(c: &mut Criterion) {
+    let depths = d1::Day1::parse(d1::INPUT).unwrap();
+    let mut group = c.benchmark_group("d1");
+    for scale in SCALES {
+        // repeating the readings keeps the data realistic, just more of it
+        let depths: Vec<u32> = depths.iter().copied().cycle().take(depths.len() * scale).collect();
+        group.throughput(Throughput::Elements(depths.len() as u64));
+
+        group.bench_with_input(BenchmarkId::new("part 1 window_map", scale), &depths, |b, depths| {
+            b.iter(|| black_box(depths).iter().window_map(|[prev, next]| prev < next).filter(|i| *i).count())
+        });
+        group.bench_with_input(BenchmarkId::new("part 1 tuple_windows", scale), &depths, |b, depths| {
+            b.iter(|| black_box(depths).iter().tuple_windows().filter(|(prev, next)| prev < next).count())
+        });
+        group.bench_with_input(BenchmarkId::new("part 2 window_map", scale), &depths, |b, depths| {
+            b.iter(|| {
+                black_box(depths).iter()
+                    .window_map(|[one, two, three, four]| *one + *two + *three < *two + *three + *four)
+                    .filter(|i| *i)
+                    .count()
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("part 2 tuple_windows", scale), &depths, |b, depths| {
+            b.iter(|| {
+                black_box(depths).iter()
+                    .tuple_windows()
+                    .filter(|(one, two, three, four)| *one + *two + *three < *two + *three + *four)
+                    .count()
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("part 1 rolling::count_increases", scale), &depths, |b, depths| {
+            b.iter(|| d1::rolling::count_increases(black_box(depths), NonZeroUsize::MIN))
+        });
+        group.bench_with_input(BenchmarkId::new("part 2 rolling::count_increases", scale), &depths, |b, depths| {
+            b.iter(|| d1::rolling::count_increases(black_box(depths), NonZeroUsize::new(3).expect("3 isn't 0")))
+        });
+    }
+    group.finish();
+}
+
+/// The ring buffer of `window_map` against the contiguous slices of `slice_window_map`, adding up windows of d1's
+/// depths.
+fn window_buffers(c: &mut Criterion) {
+    fn bench<const N: usize>(group: &mut BenchmarkGroup<'_, WallTime>, depths: &[u32]) {
+        group.bench_with_input(BenchmarkId::new("window_map", N), depths, |b, depths| {
+            b.iter(|| black_box(depths).iter().window_map(|window: [&&u32; N]| window.into_iter().copied().sum::<u32>()).max())
+        });
+        group.bench_with_input(BenchmarkId::new("slice_window_map", N), depths, |b, depths| {
+            b.iter(|| black_box(depths).iter().copied().slice_window_map(|window: &[u32; N]| window.iter().sum::<u32>()).max())
+        });
+    }
+
+    let depths = d1::Day1::parse(d1::INPUT).unwrap();
+    let depths: Vec<u32> = depths.iter().copied().cycle().take(depths.len() * 10).collect();
+    let mut group = c.benchmark_group("window buffers");
+    group.throughput(Throughput::Elements(depths.len() as u64));
+    bench::<3>(&mut group, &depths);
+    bench::<16>(&mut group, &depths);
+    bench::<64>(&mut group, &depths);
+    group.finish();
+}
+
+/// Every `width`-bit number exactly once, shuffled, from the same generator as `aoc gen 3v1`.
+///
+/// Random subsets aren't good enough here: d3v1 falls over when the least common bucket is empty, which a dense
+/// random sample hits quickly and the real inputs never do.
+fn d3_input(width: usize) -> String {
+    let shape = Shape { size: Some(1 << width), width: Some(width), alphabet: None };
+    aoc::gen::generate(d3v1::gen::generate, 0x2545_f491, &shape).unwrap()
+}
+
+/// d3: the in-place partitioning of d3v1 against the prefix tree of d3v2.
+fn d3_implementations(c: &mut Criterion) {
+    let mut group = c.benchmark_group("d3");
+    // d3v2 only handles even widths, so the bundled 12 bit input is the smallest one we can use
+    let inputs = [("input".to_string(), d3v1::INPUT.to_string())].into_iter()
+        .chain([12, 16, 18].map(|width| (format!("{} bits", width), d3_input(width))));
+    for (name, input) in inputs {
+        let v1 = d3v1::Day3::parse(&input).unwrap();
+        let v2 = d3v2::Day3::parse(&input).unwrap();
+        group.throughput(Throughput::Elements(v1.readings.len() as u64));
+        group.bench_with_input(BenchmarkId::new("d3v1 step/go", &name), &v1, |b, report| {
+            b.iter(|| d3v1::Day3::part_2(black_box(report)))
+        });
+        group.bench_with_input(BenchmarkId::new("d3v2 SemiCollapsedBinPrefixSet", &name), &v2, |b, report| {
+            b.iter(|| d3v2::Day3::part_2(black_box(report)))
+        });
+    }
+    group.finish();
+}
+
+/// d14: building the caches on their own and the whole cached expansion, for both parts.
+fn d14_caches(c: &mut Criterion) {
+    let problem = d14::Day14::parse(d14::INPUT).unwrap();
+    let mut group = c.benchmark_group("d14");
+    group.sample_size(10);
+    for (part, first_pass, rules_passes) in [("part 1", 1, 3), ("part 2", 3, 5)] {
+        group.bench_function(BenchmarkId::new("precalculate_caches", part), |b| {
+            b.iter(|| d14::precalculate_caches(first_pass, rules_passes, black_box(&problem.rules)))
+        });
+        // part 2 is dominated by building the caches, which doesn't depend on the template at all
+        let scales: &[usize] = if part == "part 1" { &SCALES } else { &[1] };
+        for &scale in scales {
+            let problem = d14::Problem { template: problem.template.repeat(scale), rules: problem.rules.clone() };
+            group.bench_with_input(BenchmarkId::new(format!("expand {}", part), scale), &problem, |b, problem| {
+                b.iter(|| d14::expand(black_box(problem), first_pass, rules_passes))
+            });
+        }
+    }
+    group.finish();
+}
+
+/// Parsing: `str::lines` and `FromStr` against scanning the bytes with `aoc::bytes`, on d1's decimals and d3's
+/// binary numbers.
+fn parsing(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parsing");
+    for scale in SCALES {
+        let input = d1::INPUT.repeat(scale);
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_with_input(BenchmarkId::new("d1 str::lines + u32::from_str", scale), &input, |b, input| {
+            b.iter(|| black_box(input).lines().map(|l| l.trim().parse::<u32>()).collect::<Result<Vec<_>, _>>())
+        });
+        group.bench_with_input(BenchmarkId::new("d1 aoc::bytes", scale), &input, |b, input| {
+            b.iter(|| {
+                aoc::bytes::lines(black_box(input).as_bytes())
+                    .map(|(_, l)| <u32 as aoc::bytes::Decimal>::parse_decimal(l.trim_ascii()))
+                    .collect::<Option<Vec<_>>>()
+            })
+        });
+
+        let input = d3v1::INPUT.repeat(scale);
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_with_input(BenchmarkId::new("d3 BufRead::lines + from_str_radix", scale), &input, |b, input| {
+            b.iter(|| {
+                BufReader::new(black_box(input).as_bytes())
+                    .lines()
+                    .map(|l| l.ok().and_then(|l| u32::from_str_radix(&l, 2).ok()))
+                    .collect::<Option<Vec<_>>>()
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("d3 aoc::bytes", scale), &input, |b, input| {
+            b.iter(|| aoc::bytes::lines(black_box(input).as_bytes()).map(|(_, l)| aoc::bytes::binary(l)).collect::<Option<Vec<_>>>())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, d1_windows, window_buffers, d3_implementations, d14_caches, parsing);
+criterion_main!(benches);
@@ -0,0 +1 @@
+//! Criterion benchmarks for every solver live in `benches/`, run them with `cargo bench -p benches`.
@@ -0,0 +1,173 @@
+//! The kinds of number that a sonar sweep can be made of.
+
+use std::{
+    cmp::Ordering,
+    fmt::Debug,
+    ops::{Add, Sub},
+};
+
+use aoc::bytes::Decimal;
+
+/// A single reading, which windows can be added up and compared over.
+pub trait Depth: Copy + Debug + Send + Sync + 'static {
+    /// What a window of depths is added up into, wide enough that no window that fits in memory can overflow it.
+    type Sum: Copy + Debug + Default + Add<Output = Self::Sum> + Sub<Output = Self::Sum>;
+
+    /// Parse the whole of `bytes`, which has already had any whitespace trimmed off, as a depth.
+    fn parse_bytes(bytes: &[u8]) -> Option<Self>;
+    /// This depth as a sum of one depth.
+    fn to_sum(self) -> Self::Sum;
+    /// Add `self` to a rolling `sum`, or take it away if `add` is false. `error` is whatever rounding has lost from
+    /// `sum` so far, for sums that round at all, so that the sum really is `sum + error`.
+    fn roll(self, sum: &mut Self::Sum, error: &mut Self::Sum, add: bool);
+    /// A total order on depths, which floats don't get from `Ord`.
+    fn total_cmp(&self, other: &Self) -> Ordering;
+    /// A total order on sums.
+    fn cmp_sums(a: &Self::Sum, b: &Self::Sum) -> Ordering;
+    fn to_f64(self) -> f64;
+    fn sum_to_f64(sum: Self::Sum) -> f64;
+    /// The depth `step` of the way along a straight line from `from` to `to` that's `steps` long, rounded to the
+    /// nearest depth there is.
+    fn lerp(from: Self, to: Self, step: usize, steps: usize) -> Self;
+}
+
+macro_rules! integer_depth {
+    ($($depth:ty => $sum:ty),*) => {$(
+        impl Depth for $depth {
+            type Sum = $sum;
+            fn parse_bytes(bytes: &[u8]) -> Option<Self> {
+                Self::parse_decimal(bytes)
+            }
+
+            fn to_sum(self) -> Self::Sum {
+                <$sum>::from(self)
+            }
+
+            fn roll(self, sum: &mut Self::Sum, _error: &mut Self::Sum, add: bool) {
+                // exact, so there's never an error
+                *sum = if add { *sum + self.to_sum() } else { *sum - self.to_sum() };
+            }
+
+            fn total_cmp(&self, other: &Self) -> Ordering {
+                self.cmp(other)
+            }
+
+            fn cmp_sums(a: &Self::Sum, b: &Self::Sum) -> Ordering {
+                a.cmp(b)
+            }
+
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+
+            fn sum_to_f64(sum: Self::Sum) -> f64 {
+                sum as f64
+            }
+
+            fn lerp(from: Self, to: Self, step: usize, steps: usize) -> Self {
+                // exactly, in something wide enough for the difference between any two depths
+                let offset = (i128::from(to) - i128::from(from)) * step as i128;
+                let steps = steps as i128;
+                // rounding half away from zero, the same as `f64::round`
+                let rounded = (2 * offset + offset.signum() * steps) / (2 * steps);
+                // it's between `from` and `to` so it always fits
+                (i128::from(from) + rounded) as Self
+            }
+        }
+    )*};
+}
+
+integer_depth!(u32 => u64, i32 => i64, u64 => u128, i64 => i128);
+
+impl Depth for f64 {
+    // adding up floats doesn't overflow, it just gets less precise
+    type Sum = f64;
+
+    fn parse_bytes(bytes: &[u8]) -> Option<Self> {
+        // floats are too fiddly to parse by hand, but at least we only need to check the one line is UTF-8. `NaN` and
+        // `inf` parse too, but they aren't depths a sonar could have measured
+        std::str::from_utf8(bytes).ok()?.parse().ok().filter(|depth: &f64| depth.is_finite())
+    }
+
+    fn to_sum(self) -> Self::Sum {
+        self
+    }
+
+    fn roll(self, sum: &mut Self::Sum, error: &mut Self::Sum, add: bool) {
+        // Neumaier's compensated summation: adding and taking away the same depths over and over would otherwise pile
+        // up rounding errors until equal windows stopped comparing equal
+        let depth = if add { self } else { -self };
+        let rounded = *sum + depth;
+        // nothing's lost to rounding an infinity, and working out what was would just make the error NaN
+        if rounded.is_finite() {
+            *error += if sum.abs() >= depth.abs() { (*sum - rounded) + depth } else { (depth - rounded) + *sum };
+        }
+        *sum = rounded;
+    }
+
+    fn total_cmp(&self, other: &Self) -> Ordering {
+        f64::total_cmp(self, other)
+    }
+
+    fn cmp_sums(a: &Self::Sum, b: &Self::Sum) -> Ordering {
+        a.total_cmp(b)
+    }
+
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn sum_to_f64(sum: Self::Sum) -> f64 {
+        sum
+    }
+
+    fn lerp(from: Self, to: Self, step: usize, steps: usize) -> Self {
+        from + (to - from) * step as f64 / steps as f64
+    }
+}
+
+/// A depth ordered by [`Depth::total_cmp`], so that floats can go in heaps and maps too.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Total<D>(pub D);
+
+impl<D: Depth> PartialEq for Total<D> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<D: Depth> Eq for Total<D> {}
+
+impl<D: Depth> PartialOrd for Total<D> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<D: Depth> Ord for Total<D> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn lerp_rounds_to_nearest() {
+        assert_eq!(u32::lerp(110, 140, 1, 3), 120);
+        assert_eq!(i64::lerp(0, -3, 1, 2), -2);
+        assert_eq!(i64::lerp(-1, 2, 1, 2), 1);
+        assert_eq!(u64::lerp(u64::MAX, 0, 1, 2), u64::MAX / 2);
+        assert_eq!(f64::lerp(0.0, 1.0, 1, 4), 0.25);
+    }
+
+    #[test]
+    fn only_finite_floats_are_depths() {
+        assert_eq!(f64::parse_bytes(b"-1.5e3"), Some(-1500.0));
+        for bytes in [&b"NaN"[..], b"inf", b"-infinity", b"1e999"] {
+            assert_eq!(f64::parse_bytes(bytes), None, "{}", String::from_utf8_lossy(bytes));
+        }
+    }
+}
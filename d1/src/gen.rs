@@ -0,0 +1,15 @@
+use aoc::{gen::Shape, Error};
+use rand::{Rng, RngCore};
+
+/// A sonar sweep: a random walk of depths that drifts downwards like the real ones do.
+pub fn generate(rng: &mut dyn RngCore, shape: &Shape) -> Result<String, Error> {
+    let size = shape.size.unwrap_or(2000);
+    let mut depth: u32 = rng.gen_range(100..200);
+    let mut input = String::new();
+    for _ in 0..size {
+        input.push_str(&depth.to_string());
+        input.push('\n');
+        depth = depth.saturating_add_signed(rng.gen_range(-10..=20));
+    }
+    Ok(input)
+}
@@ -0,0 +1,166 @@
+use std::num::NonZeroUsize;
+
+use aoc::{Error, Solution};
+pub use depth::Depth;
+
+pub mod depth;
+pub mod gen;
+pub mod parallel;
+pub mod report;
+pub mod rolling;
+pub mod stream;
+
+pub struct Day1;
+
+pub const INPUT: &str = include_str!("input.txt");
+pub const TEST: &str = include_str!("test.txt");
+
+impl aoc::Solution for Day1 {
+    const DAY: u8 = 1;
+    type Input = Vec<u32>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        // every line has to be a depth, dropping one would shift every window after it
+        match parse_with::<u32>(input, Mode::Strict) {
+            Ok(sweep) => Ok(sweep.segments.concat()),
+            Err(mut errors) => Err(errors.remove(0)),
+        }
+    }
+
+    // Both go through the rolling sums, which add up in a `u64` so that even a window of `u32::MAX`s can't overflow
+    fn part_1(depths: &Self::Input) -> Result<Self::Part1, Error> {
+        Ok(rolling::count_increases(depths, NonZeroUsize::MIN))
+    }
+
+    fn part_2(depths: &Self::Input) -> Result<Self::Part2, Error> {
+        Ok(rolling::count_increases(depths, NonZeroUsize::new(3).expect("3 isn't 0")))
+    }
+}
+
+/// What to do about lines that aren't depths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Fail, with every line that wasn't a depth.
+    Strict,
+    /// Leave them out and carry on as though they weren't there.
+    Skip,
+    /// Leave them out, and start again from scratch after them so that no window spans the gap.
+    Break,
+    /// Fill them in with a straight line between the depths either side. Lines before the first depth or after the
+    /// last have nothing to draw the line to, so those are left out instead.
+    Interpolate,
+}
+
+/// The depths from a sonar sweep that might have had some bad lines in it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sweep<D> {
+    /// Runs of depths that windows can span, only ever more than one of them in [`Mode::Break`].
+    pub segments: Vec<Vec<D>>,
+    /// Every line that wasn't a depth.
+    pub bad: Vec<Error>,
+    /// How many of the `bad` lines were filled in by [`Mode::Interpolate`] rather than left out.
+    pub interpolated: usize,
+}
+
+impl<D> Sweep<D> {
+    /// How many lines were left out altogether.
+    pub fn skipped(&self) -> usize {
+        self.bad.len() - self.interpolated
+    }
+}
+
+/// Parse a sonar sweep, dealing with lines that aren't depths according to `mode`.
+///
+/// In [`Mode::Strict`] the errors are every bad line rather than just the first, so they can all be fixed in one go.
+///
+/// `input` can be raw bytes as well as a `str`, so a memory-mapped file can be parsed without checking it's UTF-8.
+pub fn parse_with<D: Depth>(input: impl AsRef<[u8]>, mode: Mode) -> Result<Sweep<D>, Vec<Error>> {
+    let mut sweep = Sweep { segments: vec![vec![]], bad: vec![], interpolated: 0 };
+    // how many bad lines there have been since the last depth
+    let mut gap = 0;
+    for (number, line) in aoc::bytes::lines(input.as_ref()) {
+        let segment = sweep.segments.last_mut().expect("there's always a segment to add to");
+        match D::parse_bytes(line.trim_ascii()) {
+            Some(depth) => {
+                if let (Mode::Interpolate, Some(&last)) = (mode, segment.last()) {
+                    segment.extend((1..=gap).map(|step| D::lerp(last, depth, step, gap + 1)));
+                    sweep.interpolated += gap;
+                }
+                segment.push(depth);
+                gap = 0;
+            }
+            None => {
+                let line = String::from_utf8_lossy(line);
+                sweep.bad.push(Error::malformed(Day1::DAY, number, &line, "a depth"));
+                if mode == Mode::Break && !segment.is_empty() {
+                    sweep.segments.push(vec![]);
+                }
+                gap += 1;
+            }
+        }
+    }
+    if mode == Mode::Strict && !sweep.bad.is_empty() {
+        return Err(sweep.bad);
+    }
+    if mode == Mode::Break {
+        sweep.segments.retain(|segment| !segment.is_empty());
+    }
+    Ok(sweep)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc::differential;
+    use proptest::{collection::vec, prop_oneof};
+
+    // The obvious way, with slice windows and adding up every window from scratch
+    struct Naive;
+
+    impl Solution for Naive {
+        const DAY: u8 = 1;
+        type Input = Vec<u32>;
+        type Part1 = usize;
+        type Part2 = usize;
+
+        fn parse(input: &str) -> Result<Self::Input, Error> {
+            Ok(input.lines().filter_map(|l| l.parse().ok()).collect())
+        }
+
+        fn part_1(depths: &Self::Input) -> Result<Self::Part1, Error> {
+            Ok(depths.windows(2).filter(|w| w[0] < w[1]).count())
+        }
+
+        fn part_2(depths: &Self::Input) -> Result<Self::Part2, Error> {
+            let sums: Vec<u64> = depths.windows(3).map(|w| w.iter().map(|&d| u64::from(d)).sum()).collect();
+            Ok(sums.windows(2).filter(|w| w[0] < w[1]).count())
+        }
+    }
+
+    #[test]
+    fn lenient_modes() {
+        let input = "x\n100\n110\n\nbad\n140\n150\nbad\n";
+        let errors = parse_with::<u32>(input, Mode::Strict).unwrap_err();
+        assert_eq!(errors.iter().map(|e| e.line).collect::<Vec<_>>(), [Some(1), Some(4), Some(5), Some(8)]);
+        assert_eq!(errors[2], Error::malformed(1, 5, "bad", "a depth"));
+
+        let skip = parse_with::<u32>(input, Mode::Skip).unwrap();
+        assert_eq!(skip.segments, [vec![100, 110, 140, 150]]);
+        assert_eq!(skip.skipped(), 4);
+        let split = parse_with::<i64>(input, Mode::Break).unwrap();
+        assert_eq!(split.segments, [vec![100, 110], vec![140, 150]]);
+        assert_eq!(split.skipped(), 4);
+        let interpolated = parse_with::<u64>(input, Mode::Interpolate).unwrap();
+        assert_eq!(interpolated.segments, [vec![100, 110, 120, 130, 140, 150]]);
+        assert_eq!((interpolated.interpolated, interpolated.skipped()), (2, 2));
+    }
+
+    #[test]
+    fn matches_naive() {
+        // big depths too, where adding up a window overflows a `u32`
+        let depths = prop_oneof![0..10_000u32, u32::MAX - 10_000..=u32::MAX];
+        differential::assert_agree::<Naive, Day1>(None, differential::lines(vec(depths, 0..100), u32::to_string));
+    }
+}
@@ -0,0 +1,80 @@
+//! Counting increases on every core at once, for sweeps too long to want to wait for one core to get through.
+
+use std::num::NonZeroUsize;
+
+use rayon::prelude::*;
+
+use crate::{depth::Depth, rolling::count_increases};
+
+/// The same as [`count_increases`], but counted in parallel with chunks sized to keep every thread busy.
+pub fn count_increases_par<D: Depth>(depths: &[D], size: NonZeroUsize) -> usize {
+    // a few chunks per thread so that one slow thread doesn't hold everything up, but not so many that the overlap
+    // between them starts to cost more than it saves
+    let chunk = (depths.len() / (rayon::current_num_threads() * 4)).max(1 << 12);
+    count_increases_chunked(depths, size, NonZeroUsize::new(chunk).expect("it's at least 4096"))
+}
+
+/// The same as [`count_increases`], but with each `chunk` comparisons counted in parallel.
+///
+/// Comparing window `i` with window `i + 1` needs the `size + 1` depths from `i` onwards, so each chunk's slice of
+/// `depths` carries on `size` depths past where the next chunk starts. Those are the only depths that are looked at
+/// twice, and every comparison is made by exactly one chunk.
+pub fn count_increases_chunked<D: Depth>(depths: &[D], size: NonZeroUsize, chunk: NonZeroUsize) -> usize {
+    let comparisons = depths.len().saturating_sub(size.get());
+    let chunks = comparisons.div_ceil(chunk.get());
+    (0..chunks).into_par_iter()
+        .map(|i| {
+            let start = i * chunk.get();
+            let end = (start + chunk.get()).min(comparisons);
+            count_increases(&depths[start..end + size.get()], size)
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Day1, INPUT};
+    use aoc::{differential, Error, Solution};
+    use proptest::collection::vec;
+
+    // Chunks as small as they go, so that nearly every comparison is next to a boundary
+    struct Chunked;
+
+    impl Solution for Chunked {
+        const DAY: u8 = 1;
+        type Input = Vec<u32>;
+        type Part1 = usize;
+        type Part2 = usize;
+
+        fn parse(input: &str) -> Result<Self::Input, Error> {
+            Day1::parse(input)
+        }
+
+        fn part_1(depths: &Self::Input) -> Result<Self::Part1, Error> {
+            Ok(count_increases_chunked(depths, NonZeroUsize::MIN, NonZeroUsize::MIN))
+        }
+
+        fn part_2(depths: &Self::Input) -> Result<Self::Part2, Error> {
+            Ok(count_increases_chunked(depths, NonZeroUsize::new(3).unwrap(), NonZeroUsize::new(2).unwrap()))
+        }
+    }
+
+    #[test]
+    fn chunked_matches_sequential() {
+        differential::assert_agree::<Day1, Chunked>(None, differential::lines(vec(0..100u32, 0..50), u32::to_string));
+    }
+
+    #[test]
+    fn every_chunk_size_matches_sequential() -> Result<(), Error> {
+        let depths = Day1::parse(INPUT)?;
+        for size in (1..8).map(|size| NonZeroUsize::new(size).unwrap()) {
+            let expected = count_increases(&depths, size);
+            assert_eq!(count_increases_par(&depths, size), expected);
+            for chunk in [1, 2, 3, 7, 100, 1999, 2000, 5000].map(|chunk| NonZeroUsize::new(chunk).unwrap()) {
+                assert_eq!(count_increases_chunked(&depths, size, chunk), expected, "window {}, chunks of {}", size, chunk);
+            }
+        }
+        Ok(())
+    }
+}
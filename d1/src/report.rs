@@ -0,0 +1,180 @@
+//! Everything about how a sonar sweep goes up and down, not just how often.
+
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display},
+    num::NonZeroUsize,
+};
+
+use aoc::WindowMapExt;
+use serde::Serialize;
+
+use crate::{depth::Depth, rolling::RollingExt};
+
+/// Which way the sum of the window went between one window and the next.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Trend {
+    Rising,
+    Falling,
+    Flat,
+}
+
+impl Trend {
+    fn between<D: Depth>(prev: &D::Sum, next: &D::Sum) -> Self {
+        match D::cmp_sums(next, prev) {
+            Ordering::Greater => Trend::Rising,
+            Ordering::Less => Trend::Falling,
+            Ordering::Equal => Trend::Flat,
+        }
+    }
+}
+
+impl Display for Trend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // `pad` rather than `write!` so that it lines up in the table
+        f.pad(match self {
+            Trend::Rising => "rising",
+            Trend::Falling => "falling",
+            Trend::Flat => "flat",
+        })
+    }
+}
+
+/// A stretch of windows that all went the same way.
+///
+/// Like everywhere else in a [`Report`], windows are identified by the index of the last depth in them, so that
+/// the positions mean the same thing whatever size the window is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Segment {
+    pub trend: Trend,
+    /// The window the stretch starts from, which is the last window of the segment before.
+    pub start: usize,
+    /// The last window in the stretch, inclusive.
+    pub end: usize,
+}
+
+impl Segment {
+    /// How many windows there are in the segment, counting both ends.
+    pub fn windows(&self) -> usize {
+        self.end - self.start + 1
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Report {
+    /// How many depths are added up in each window.
+    pub window: usize,
+    /// How many windows there were.
+    pub windows: usize,
+    /// Every window that was deeper than the one before it.
+    pub increases: Vec<usize>,
+    /// The first of the longest runs of windows that each got deeper. A single window counts as a run of one.
+    pub longest_increasing: Option<Segment>,
+    /// The first of the longest runs of windows that each got shallower.
+    pub longest_decreasing: Option<Segment>,
+    /// The whole sweep, split up wherever the trend changes.
+    pub segments: Vec<Segment>,
+}
+
+/// Report on the windows of `size` depths.
+pub fn report<D: Depth>(depths: &[D], size: NonZeroUsize) -> Report {
+    let sums: Vec<D::Sum> = depths.iter().copied().rolling_sum(size).collect();
+    // from the index of a window's first depth to the index of its last
+    let offset = size.get() - 1;
+
+    let mut segments: Vec<Segment> = vec![];
+    for (i, trend) in sums.iter().window_map(|[prev, next]| Trend::between::<D>(prev, next)).enumerate() {
+        let end = i + 1 + offset;
+        match segments.last_mut() {
+            Some(segment) if segment.trend == trend => segment.end = end,
+            _ => segments.push(Segment { trend, start: i + offset, end }),
+        }
+    }
+
+    let increases = segments.iter()
+        .filter(|segment| segment.trend == Trend::Rising)
+        .flat_map(|segment| segment.start + 1..=segment.end)
+        .collect();
+    let longest = |trend| {
+        let first = (!sums.is_empty()).then_some(Segment { trend, start: offset, end: offset });
+        segments.iter()
+            .filter(|segment| segment.trend == trend)
+            .fold(first, |longest, &segment| match longest {
+                Some(longest) if longest.windows() >= segment.windows() => Some(longest),
+                _ => Some(segment),
+            })
+    };
+
+    Report {
+        window: size.get(),
+        windows: sums.len(),
+        increases,
+        longest_increasing: longest(Trend::Rising),
+        longest_decreasing: longest(Trend::Falling),
+        segments,
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "window of {}, {} windows, {} increases", self.window, self.windows, self.increases.len())?;
+        let run = |run: &Option<Segment>| match run {
+            Some(run) => format!("{} windows, {} to {}", run.windows(), run.start, run.end),
+            None => "none".to_string(),
+        };
+        writeln!(f, "longest increasing run: {}", run(&self.longest_increasing))?;
+        writeln!(f, "longest decreasing run: {}", run(&self.longest_decreasing))?;
+        let increases: Vec<String> = self.increases.iter().map(usize::to_string).collect();
+        writeln!(f, "increases at: {}", increases.join(", "))?;
+        writeln!(f)?;
+        writeln!(f, "{:<8} {:>8} {:>8} {:>8}", "trend", "start", "end", "windows")?;
+        for segment in &self.segments {
+            writeln!(f, "{:<8} {:>8} {:>8} {:>8}", segment.trend, segment.start, segment.end, segment.windows())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Day1, TEST};
+    use aoc::Solution;
+
+    #[test]
+    fn reports_test_input() -> Result<(), aoc::Error> {
+        let depths = Day1::parse(TEST)?;
+        // 199 200 208 210 200 207 240 269 260 263
+        let report = report(&depths, NonZeroUsize::MIN);
+        assert_eq!(report.windows, 10);
+        assert_eq!(report.increases, [1, 2, 3, 5, 6, 7, 9]);
+        assert_eq!(report.longest_increasing, Some(Segment { trend: Trend::Rising, start: 0, end: 3 }));
+        assert_eq!(report.longest_decreasing, Some(Segment { trend: Trend::Falling, start: 3, end: 4 }));
+        let trends: Vec<_> = report.segments.iter().map(|s| (s.trend, s.start, s.end)).collect();
+        assert_eq!(trends, [
+            (Trend::Rising, 0, 3),
+            (Trend::Falling, 3, 4),
+            (Trend::Rising, 4, 7),
+            (Trend::Falling, 7, 8),
+            (Trend::Rising, 8, 9),
+        ]);
+
+        // the sums are 607 618 618 617 647 716 769 792, with positions shifted along to the last depth of each
+        let report = super::report(&depths, NonZeroUsize::new(3).unwrap());
+        assert_eq!(report.increases.len(), Day1::part_2(&depths)?);
+        assert_eq!(report.increases, [3, 6, 7, 8, 9]);
+        assert_eq!(report.segments[1], Segment { trend: Trend::Flat, start: 3, end: 4 });
+        assert_eq!(report.longest_decreasing, Some(Segment { trend: Trend::Falling, start: 4, end: 5 }));
+        Ok(())
+    }
+
+    #[test]
+    fn too_few_depths() {
+        let report = report(&[1, 2], NonZeroUsize::new(3).unwrap());
+        assert_eq!((report.windows, report.longest_increasing), (0, None));
+        let report = super::report(&[1], NonZeroUsize::MIN);
+        assert_eq!(report.longest_increasing, Some(Segment { trend: Trend::Rising, start: 0, end: 0 }));
+        assert!(report.segments.is_empty());
+    }
+}
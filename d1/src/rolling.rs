@@ -0,0 +1,408 @@
+//! Windows whose length is only known at runtime, kept up to date one depth at a time rather than re-added.
+//!
+//! These don't build on `window_map`: its windows are sized at compile time and handed over whole, so anything on top
+//! of it would have to look at every depth in every window. Instead each adapter keeps just the state its own
+//! statistic needs, a running sum, a monotonic deque or a pair of heaps, so that every window costs the same (or for
+//! the median, the log of the size) however big it is.
+
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::{BTreeMap, BinaryHeap, VecDeque},
+    num::NonZeroUsize,
+};
+
+use aoc::WindowMapExt;
+
+use crate::depth::{Depth, Total};
+
+pub trait RollingExt<D: Depth>: Iterator<Item = D> + Sized {
+    /// The sum of every window of `size` consecutive depths.
+    fn rolling_sum(self, size: NonZeroUsize) -> RollingSum<D, Self> {
+        let window = VecDeque::with_capacity(size.get() + 1);
+        RollingSum { depths: self, size, window, sum: D::Sum::default(), error: D::Sum::default() }
+    }
+
+    /// The mean of every window of `size` consecutive depths.
+    fn rolling_mean(self, size: NonZeroUsize) -> RollingMean<D, Self> {
+        RollingMean(self.rolling_sum(size))
+    }
+
+    /// The shallowest depth in every window of `size` consecutive depths.
+    fn rolling_min(self, size: NonZeroUsize) -> RollingExtreme<D, Self> {
+        RollingExtreme { depths: self, size, seen: 0, candidates: VecDeque::new(), extreme: Extreme::Min }
+    }
+
+    /// The deepest depth in every window of `size` consecutive depths.
+    fn rolling_max(self, size: NonZeroUsize) -> RollingExtreme<D, Self> {
+        RollingExtreme { depths: self, size, seen: 0, candidates: VecDeque::new(), extreme: Extreme::Max }
+    }
+
+    /// The median of every window of `size` consecutive depths, halfway between the middle two for even sizes.
+    fn rolling_median(self, size: NonZeroUsize) -> RollingMedian<D, Self> {
+        RollingMedian {
+            depths: self,
+            size,
+            window: VecDeque::with_capacity(size.get() + 1),
+            lower: BinaryHeap::new(),
+            upper: BinaryHeap::new(),
+            lower_len: 0,
+            upper_len: 0,
+            removed: BTreeMap::new(),
+        }
+    }
+}
+impl<D: Depth, I: Iterator<Item = D>> RollingExt<D> for I {}
+
+/// The sum of each window, kept up to date by adding the newest depth and taking away the oldest so that each window
+/// costs the same however big it is. Floats included, since [`Depth::roll`] compensates for their rounding.
+pub struct RollingSum<D: Depth, I> {
+    depths: I,
+    size: NonZeroUsize,
+    window: VecDeque<D>,
+    /// The sum of `window` as it's been added up, which for floats has lost `error` to rounding along the way.
+    sum: D::Sum,
+    error: D::Sum,
+}
+
+impl<D: Depth, I: Iterator<Item = D>> Iterator for RollingSum<D, I> {
+    type Item = D::Sum;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let depth = self.depths.next()?;
+            self.window.push_back(depth);
+            depth.roll(&mut self.sum, &mut self.error, true);
+            match self.window.len().cmp(&self.size.get()) {
+                // still filling up the first window
+                Ordering::Less => continue,
+                Ordering::Equal => {}
+                Ordering::Greater => {
+                    let oldest = self.window.pop_front().expect("the window can't be empty, we just pushed to it");
+                    oldest.roll(&mut self.sum, &mut self.error, false);
+                }
+            }
+            // once an infinity or a NaN has gone in, taking it back out can't get rid of it (`inf - inf` is NaN), so
+            // add up what's left from scratch instead until it's gone
+            if !(D::sum_to_f64(self.sum).is_finite() && D::sum_to_f64(self.error).is_finite()) {
+                (self.sum, self.error) = (D::Sum::default(), D::Sum::default());
+                for depth in &self.window {
+                    depth.roll(&mut self.sum, &mut self.error, true);
+                }
+            }
+            return Some(self.sum + self.error);
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        windows_left(self.size, self.window.len(), self.depths.size_hint())
+    }
+}
+
+pub struct RollingMean<D: Depth, I>(RollingSum<D, I>);
+
+impl<D: Depth, I: Iterator<Item = D>> Iterator for RollingMean<D, I> {
+    type Item = f64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let sum = self.0.next()?;
+        Some(D::sum_to_f64(sum) / self.0.size.get() as f64)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+/// Which end of each window a [`RollingExtreme`] is after.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Extreme {
+    Min,
+    Max,
+}
+
+impl Extreme {
+    /// Whether `new` coming along means `old` can never be the extreme of a window again. Ties go to `new`, since it
+    /// stays in the window for longer.
+    fn replaces<D: Depth>(self, old: &D, new: &D) -> bool {
+        let ordering = old.total_cmp(new);
+        match self {
+            Extreme::Min => ordering.is_ge(),
+            Extreme::Max => ordering.is_le(),
+        }
+    }
+}
+
+/// The min or max of each window, from a monotonic deque.
+///
+/// `candidates` only holds the depths that could still be the extreme of some window: any depth that's beaten by a
+/// later one can never be the extreme again, because the later one will be in every window it is. So the deque is
+/// sorted, its front is the current extreme, and every depth is pushed and popped at most once.
+pub struct RollingExtreme<D, I> {
+    depths: I,
+    size: NonZeroUsize,
+    /// How many depths we've taken, so the index the next one will have.
+    seen: usize,
+    /// The index and depth of everything that could still be the extreme.
+    candidates: VecDeque<(usize, D)>,
+    extreme: Extreme,
+}
+
+impl<D: Depth, I: Iterator<Item = D>> Iterator for RollingExtreme<D, I> {
+    type Item = D;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let depth = self.depths.next()?;
+            let index = self.seen;
+            self.seen += 1;
+
+            while let Some((_, last)) = self.candidates.back() {
+                if !self.extreme.replaces(last, &depth) {
+                    break;
+                }
+                self.candidates.pop_back();
+            }
+            self.candidates.push_back((index, depth));
+            // the front has slid out of the window
+            if let Some(&(first, _)) = self.candidates.front() {
+                if first + self.size.get() <= index {
+                    self.candidates.pop_front();
+                }
+            }
+
+            if self.seen >= self.size.get() {
+                let (_, extreme) = self.candidates.front().expect("we just pushed to it");
+                return Some(*extreme);
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        windows_left(self.size, self.seen, self.depths.size_hint())
+    }
+}
+
+/// The median of each window, from two heaps that split the window in half.
+///
+/// Everything in `lower` is no deeper than everything in `upper`, and `lower` has the extra one if the window is odd,
+/// so the median is on top of one or both of them. Depths that slide out of the window can't be taken out of the
+/// middle of a heap so they're only counted in `removed`, and popped for real once they get to the top.
+pub struct RollingMedian<D, I> {
+    depths: I,
+    size: NonZeroUsize,
+    window: VecDeque<D>,
+    lower: BinaryHeap<Total<D>>,
+    upper: BinaryHeap<Reverse<Total<D>>>,
+    /// How many depths in each heap are really still in the window.
+    lower_len: usize,
+    upper_len: usize,
+    /// Depths that have left the window but are still in one of the heaps, and how many times.
+    removed: BTreeMap<Total<D>, usize>,
+}
+
+impl<D: Depth, I> RollingMedian<D, I> {
+    fn insert(&mut self, depth: D) {
+        let depth = Total(depth);
+        if self.lower.peek().is_none_or(|&top| depth <= top) {
+            self.lower.push(depth);
+            self.lower_len += 1;
+        } else {
+            self.upper.push(Reverse(depth));
+            self.upper_len += 1;
+        }
+        self.rebalance();
+    }
+
+    fn remove(&mut self, depth: D) {
+        let depth = Total(depth);
+        *self.removed.entry(depth).or_default() += 1;
+        // the tops are always still in the window, so this tells us which heap it's in
+        if self.lower.peek().is_some_and(|&top| depth <= top) {
+            self.lower_len -= 1;
+            self.prune_lower();
+        } else {
+            self.upper_len -= 1;
+            self.prune_upper();
+        }
+        self.rebalance();
+    }
+
+    fn rebalance(&mut self) {
+        if self.lower_len > self.upper_len + 1 {
+            let top = self.lower.pop().expect("lower has more in it than upper");
+            self.upper.push(Reverse(top));
+            self.lower_len -= 1;
+            self.upper_len += 1;
+            self.prune_lower();
+        } else if self.lower_len < self.upper_len {
+            let Reverse(top) = self.upper.pop().expect("upper has more in it than lower");
+            self.lower.push(top);
+            self.upper_len -= 1;
+            self.lower_len += 1;
+            self.prune_upper();
+        }
+    }
+
+    fn prune_lower(&mut self) {
+        while let Some(&top) = self.lower.peek() {
+            if !take_removed(&mut self.removed, top) {
+                break;
+            }
+            self.lower.pop();
+        }
+    }
+
+    fn prune_upper(&mut self) {
+        while let Some(&Reverse(top)) = self.upper.peek() {
+            if !take_removed(&mut self.removed, top) {
+                break;
+            }
+            self.upper.pop();
+        }
+    }
+}
+
+/// If `depth` is waiting to be removed then count it as removed now.
+fn take_removed<D: Depth>(removed: &mut BTreeMap<Total<D>, usize>, depth: Total<D>) -> bool {
+    match removed.get_mut(&depth) {
+        Some(count) => {
+            *count -= 1;
+            if *count == 0 {
+                removed.remove(&depth);
+            }
+            true
+        }
+        None => false,
+    }
+}
+
+impl<D: Depth, I: Iterator<Item = D>> Iterator for RollingMedian<D, I> {
+    type Item = f64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let depth = self.depths.next()?;
+            self.window.push_back(depth);
+            self.insert(depth);
+            match self.window.len().cmp(&self.size.get()) {
+                Ordering::Less => continue,
+                Ordering::Equal => {}
+                Ordering::Greater => {
+                    let oldest = self.window.pop_front().expect("the window can't be empty, we just pushed to it");
+                    self.remove(oldest);
+                }
+            }
+            let Total(lower) = self.lower.peek().expect("the window isn't empty");
+            return Some(if self.size.get() % 2 == 1 {
+                lower.to_f64()
+            } else {
+                let Reverse(Total(upper)) = self.upper.peek().expect("the window has at least two depths in it");
+                (lower.to_f64() + upper.to_f64()) / 2.0
+            });
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        windows_left(self.size, self.window.len(), self.depths.size_hint())
+    }
+}
+
+/// How many more windows of `size` there'll be after `taken` depths, given the size hint of the depths still to come:
+/// one for each of them, apart from any that the first window still needs.
+fn windows_left(size: NonZeroUsize, taken: usize, (lower, upper): (usize, Option<usize>)) -> (usize, Option<usize>) {
+    let missing = size.get().saturating_sub(taken + 1);
+    (lower.saturating_sub(missing), upper.map(|upper| upper.saturating_sub(missing)))
+}
+
+/// How many times the sum of a `size`-depth window is bigger than the one before it.
+///
+/// A `size` of 1 is part 1 and 3 is part 2.
+pub fn count_increases<D: Depth>(depths: &[D], size: NonZeroUsize) -> usize {
+    depths.iter()
+        .copied()
+        .rolling_sum(size)
+        .window_map(|[prev, next]| D::cmp_sums(prev, next) == Ordering::Less)
+        .filter(|i| *i)
+        .count()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Day1, INPUT};
+    use aoc::Solution;
+
+    #[test]
+    fn count_increases_matches_slice_windows() -> Result<(), aoc::Error> {
+        let depths = Day1::parse(INPUT)?;
+        assert_eq!(count_increases(&depths, NonZeroUsize::MIN), Day1::part_1(&depths)?);
+        for size in 1..10 {
+            let sums: Vec<u32> = depths.windows(size).map(|w| w.iter().sum()).collect();
+            let expected = sums.windows(2).filter(|w| w[0] < w[1]).count();
+            let size = NonZeroUsize::new(size).expect("sizes start at 1");
+            assert_eq!(count_increases(&depths, size), expected, "window of {}", size);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn other_kinds_of_depth() {
+        let size = |size| NonZeroUsize::new(size).expect("sizes start at 1");
+        assert_eq!(count_increases(&[-3i64, -2, -2, 5, i64::MIN], size(1)), 2);
+        // these would overflow a `u64` sum
+        assert_eq!(count_increases(&[0, u64::MAX, u64::MAX, 1], size(2)), 1);
+        // adding and taking away 0.1 over and over would drift, and make some of these look different
+        assert_eq!(count_increases(&[0.1; 1000], size(3)), 0);
+        // taking a huge depth back out of the sum would otherwise lose the small ones that went in with it
+        let sums: Vec<f64> = [1e17, 0.1, 0.1, 0.1].into_iter().rolling_sum(size(3)).collect();
+        assert_eq!(sums[1], 0.1 + 0.1 + 0.1);
+        assert_eq!(count_increases(&[1.5, -0.5, f64::NAN], size(1)), 1);
+        // NaN sorts above everything, but once it's out of the window it mustn't stick around in the sum
+        assert_eq!(count_increases(&[f64::NAN, 1.0, 2.0, 3.0], size(1)), 2);
+        assert_eq!(count_increases(&[f64::INFINITY, 1.0, 2.0, 3.0, 4.0], size(2)), 2);
+        let sums: Vec<f64> = [f64::NEG_INFINITY, f64::INFINITY, 1.0, 2.0].into_iter().rolling_sum(size(2)).collect();
+        assert!(sums[0].is_nan());
+        assert_eq!(sums[1..], [f64::INFINITY, 3.0]);
+        let min: Vec<f64> = [2.5, -1.0, 3.0].into_iter().rolling_min(size(2)).collect();
+        assert_eq!(min, [-1.0, -1.0]);
+        let median: Vec<f64> = [2.5, -1.0, 3.0, 0.5].into_iter().rolling_median(size(3)).collect();
+        assert_eq!(median, [2.5, 0.5]);
+    }
+
+    #[test]
+    fn rolling_stats_match_sorted_windows() -> Result<(), aoc::Error> {
+        // the real input is mostly increasing, so shuffle some of it about to give the deques and heaps some work
+        let mut depths = Day1::parse(INPUT)?;
+        depths.truncate(500);
+        let len = depths.len();
+        for i in (0..len).step_by(3) {
+            depths.swap(i, (i * 7919) % len);
+        }
+        for size in 1..12 {
+            let windows = || depths.windows(size).map(|w| {
+                let mut w = w.to_vec();
+                w.sort();
+                w
+            });
+            let size = NonZeroUsize::new(size).expect("sizes start at 1");
+            let iter = || depths.iter().copied();
+
+            let min: Vec<u32> = windows().map(|w| w[0]).collect();
+            assert_eq!(iter().rolling_min(size).collect::<Vec<_>>(), min, "window of {}", size);
+            let max: Vec<u32> = windows().map(|w| w[w.len() - 1]).collect();
+            assert_eq!(iter().rolling_max(size).collect::<Vec<_>>(), max, "window of {}", size);
+            let mean: Vec<f64> = windows().map(|w| w.iter().sum::<u32>() as f64 / w.len() as f64).collect();
+            assert_eq!(iter().rolling_mean(size).collect::<Vec<_>>(), mean, "window of {}", size);
+            let median: Vec<f64> = windows()
+                .map(|w| (f64::from(w[(w.len() - 1) / 2]) + f64::from(w[w.len() / 2])) / 2.0)
+                .collect();
+            assert_eq!(iter().rolling_median(size).collect::<Vec<_>>(), median, "window of {}", size);
+
+            let hint = (min.len(), Some(min.len()));
+            assert_eq!(iter().rolling_sum(size).size_hint(), hint, "window of {}", size);
+            assert_eq!(iter().rolling_max(size).size_hint(), hint, "window of {}", size);
+            assert_eq!(iter().rolling_median(size).size_hint(), hint, "window of {}", size);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,70 @@
+//! Counting increases as the depths arrive, for inputs that are still being written or that won't fit in memory.
+
+use std::{cmp::Ordering, io::{self, BufRead}, num::NonZeroUsize};
+
+use aoc::WindowMapExt;
+
+use crate::{depth::Depth, rolling::RollingExt};
+
+/// A window that was deeper than the one before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Increase {
+    /// The 0-based index of the depth that made the window deeper, counting only the lines that were depths.
+    pub index: usize,
+    /// How many increases there have been so far, including this one.
+    pub count: usize,
+}
+
+/// Read depths from `reader` a line at a time, calling `on_increase` as soon as each increase in the sum of a
+/// `size`-depth window is seen, and return the total.
+///
+/// Lines that aren't depths are skipped, as in [`Mode::Skip`](crate::Mode::Skip). Only the current window is kept so the
+/// input can be as long as you like.
+pub fn increases<D: Depth, R: BufRead>(mut reader: R, size: NonZeroUsize, mut on_increase: impl FnMut(Increase)) -> io::Result<usize> {
+    // stop at the first read error, but hang on to it to return once we're out of the iterator
+    let mut error = None;
+    // the one buffer for every line, so there's no allocating as we go
+    let mut line = vec![];
+    let depths = std::iter::from_fn(|| loop {
+        line.clear();
+        match reader.read_until(b'\n', &mut line) {
+            Ok(0) => return None,
+            Ok(_) => {
+                if let Some(depth) = D::parse_bytes(line.trim_ascii()) {
+                    return Some(depth);
+                }
+            }
+            Err(e) => {
+                error = Some(e);
+                return None;
+            }
+        }
+    });
+
+    let mut count = 0;
+    let comparisons = depths.rolling_sum(size).window_map(|[prev, next]| D::cmp_sums(prev, next) == Ordering::Less);
+    for (i, increased) in comparisons.enumerate() {
+        if increased {
+            count += 1;
+            // comparison `i` is between the windows starting at `i` and `i + 1`, so the new depth is `i + size`
+            on_increase(Increase { index: i + size.get(), count });
+        }
+    }
+    error.map_or(Ok(count), Err)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::TEST;
+
+    #[test]
+    fn streams_test_input() -> io::Result<()> {
+        let mut seen = vec![];
+        let total = increases::<u32, _>(TEST.as_bytes(), NonZeroUsize::MIN, |increase| seen.push(increase.index))?;
+        assert_eq!(total, 7);
+        assert_eq!(seen, [1, 2, 3, 5, 6, 7, 9]);
+        assert_eq!(increases::<u32, _>(TEST.as_bytes(), NonZeroUsize::new(3).unwrap(), |_| ())?, 5);
+        Ok(())
+    }
+}
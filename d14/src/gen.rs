@@ -0,0 +1,26 @@
+use aoc::{gen::Shape, Cause, Error};
+use rand::{seq::SliceRandom, RngCore};
+
+use crate::DAY;
+
+/// A polymer template and a complete set of pair insertion rules, one for every pair of elements in the alphabet.
+pub fn generate(rng: &mut dyn RngCore, shape: &Shape) -> Result<String, Error> {
+    let size = shape.size.unwrap_or(20);
+    let alphabet: Vec<char> = shape.alphabet.as_deref().unwrap_or("BCFHKNOPSV").chars().collect();
+    if alphabet.is_empty() || !alphabet.iter().all(char::is_ascii_alphabetic) {
+        return Err(Error::new(DAY, Cause::Unsupported("the alphabet has to be one or more letters".to_string())));
+    }
+    if size == 0 {
+        return Err(Error::new(DAY, Cause::Unsupported("the template can't be empty".to_string())));
+    }
+
+    let pick = |rng: &mut dyn RngCore| *alphabet.choose(rng).expect("we checked it wasn't empty");
+    let mut input: String = (0..size).map(|_| pick(rng)).collect();
+    input.push_str("\n\n");
+    for &left in &alphabet {
+        for &right in &alphabet {
+            input.push_str(&format!("{}{} -> {}\n", left, right, pick(rng)));
+        }
+    }
+    Ok(input)
+}
@@ -0,0 +1,362 @@
+use std::collections::{BTreeMap};
+
+use aoc::{Cause, Error};
+use nom::{character::complete::{alpha1, anychar}, bytes::complete::tag, combinator::all_consuming};
+use itertools::Itertools;
+
+type Rule<A> = BTreeMap<(char, char), A>;
+type Count = BTreeMap<char, u64>;
+
+pub mod gen;
+
+pub struct Day14;
+
+pub const INPUT: &str = include_str!("input.txt");
+pub const TEST: &str = include_str!("test.txt");
+
+const DAY: u8 = 14;
+
+impl aoc::Solution for Day14 {
+    const DAY: u8 = DAY;
+    type Input = Problem;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Problem::parse(input)
+    }
+
+    // 10 = one pass of 2^^1 and one pass of 2^^3 
+    fn part_1(problem: &Self::Input) -> Result<Self::Part1, Error> {
+        expand(problem, 1, 3)
+    }
+
+    // 40 = one pass of 2^^3 and one pass of 2^^5
+    fn part_2(problem: &Self::Input) -> Result<Self::Part2, Error> {
+        expand(problem, 3, 5)
+    }
+}
+
+/// Run `2^first_pass + 2^rules_passes` steps of pair insertion and return the most common minus the least common
+/// element count. `first_pass` has to be at least 2 less than `rules_passes`, see [`precalculate_caches`].
+pub fn expand(problem: &Problem, first_pass: usize, rules_passes: usize) -> Result<u64, Error> {
+    let caches = precalculate_caches(first_pass, rules_passes, &problem.rules)?;
+
+    let mut chars = problem.template.chars().peekable();
+    // the algo iterates over pairs, adding the 2nd of each pair, so the very first char needs to be manually added
+    let first_char = *chars.peek().ok_or(Error::new(DAY, Cause::EmptyInput))?;
+
+    // We just use a single mutable map to keep track of char counts
+    // Could probably do everything more nicely with a fold to combine the btreemaps?
+    let mut count_map: Count = BTreeMap::from([(first_char, 1)]);
+   
+    // We expand the actual rules out once, to a depth of `first_pass`, to ensure that the remaining `n` is a power of 2.
+    // Some cases might have to do it more than once but both 10 and 40 are the sum of two different powers of 2 so it's fine.
+    chars.tuple_windows().for_each(|(first, second)| {
+        let count = if let Some(expansion) = caches.first_pass_expansion.get(&(first, second)) {
+            let mut expansion_count = BTreeMap::new();
+            // Okay now we have a semi-large expansion, there's no way we could actually expand it again so instead
+            // we use the pre-calculated counts to work out what the counts of its expansion would be.
+            let full_expansion = sandwich(first, expansion, second);
+            for (inner_first, inner_second) in full_expansion.tuple_windows() {
+                let inner_count = expand_with_counts(inner_first, inner_second, &caches.final_pass_expansion_count);
+                add_counts(&mut expansion_count, &inner_count);
+            }
+            expansion_count
+        } else {
+            // If there's no rule to apply, just add the 2nd of the pair
+            BTreeMap::from([(second, 1)])
+        };
+        add_counts(&mut count_map, &count);
+    });
+
+    Ok(max_minus_min(count_map.into_values()))
+}
+
+fn max_minus_min(iter: impl Iterator<Item=u64>) -> u64 {
+    let mut min = u64::MAX;
+    let mut max = 0u64;
+    for x in iter {
+        if x < min {min = x}
+        if x > max {max = x}
+    }
+    max - min
+}
+
+pub struct PreCalculatedCaches {
+    first_pass_expansion: Rule<Vec<char>>,
+    final_pass_expansion_count: Rule<Count>,
+}
+
+/// Expand every rule out `2^first_pass` steps, and count what `2^rules_passes` steps of it would give.
+///
+/// Both come out of the same stack of ever longer expansions, and the last one in the stack gets counted rather than
+/// kept, so `first_pass` has to be at least 2 less than `rules_passes` or it's an error.
+pub fn precalculate_caches(first_pass: usize, rules_passes: usize, rules: &[(char, char, char)], ) -> Result<PreCalculatedCaches, Error> {
+    if first_pass >= rules_passes.saturating_sub(1) {
+        let reason = format!("passes of 2^{} and 2^{}, the first has to be at least 2 smaller", first_pass, rules_passes);
+        return Err(Error::new(DAY, Cause::Unsupported(reason)));
+    }
+
+    let mut rules_maps = vec![];
+
+    // Okay doing rules one at a time is for suckers let's precalculate their expansion
+    let mut rules_map = BTreeMap::new();
+    for &(k1, k2, v) in rules {
+        rules_map.insert((k1, k2), vec![v]);
+    }
+    rules_maps.push(rules_map);
+
+    // Until we get too large, (n = 6 is right out), let's expand the map ourselves
+    for i in 1..rules_passes {
+        let mut new_map = BTreeMap::new();
+        for ((first, second), old_expansion) in &rules_maps[i-1] {
+
+            let full_old_expansion = sandwich(*first, old_expansion, *second);
+            let mut new_expansion = vec![];
+            for (inner_first, inner_second) in full_old_expansion.tuple_windows() {
+                if let Some(inner_match) = rules_maps[i-1].get(&(inner_first, inner_second)) {
+                    for inner_v in inner_match {
+                        new_expansion.push(*inner_v);
+                    }
+                }
+                // a pair with no rule doesn't grow, but it's still there
+                new_expansion.push(inner_second);
+            }
+            new_expansion.pop();
+            new_map.insert((*first, *second), new_expansion);
+            
+        }
+        rules_maps.push(new_map);
+    }
+    let last_rule_map = rules_maps.remove(rules_passes-1);
+    let first_pass_rule_map = rules_maps.remove(first_pass);
+
+    // get outta here buddy
+    std::mem::drop(rules_maps);
+
+    // Okay this countmap's goal is to turn each rule from
+    // (A, E) -> "BCD"
+    // to 
+    // (A, E) ->  {B: 1, C: 1, D: 1}
+    let mut semifinal_rules_pass_count_map = BTreeMap::new();
+    for ((k0, k1), vs) in &last_rule_map {
+        let mut count_map = BTreeMap::new();
+        for v in vs {
+            map_add(&mut count_map, *v, 1);
+        }
+        semifinal_rules_pass_count_map.insert((*k0, *k1), count_map);
+    }
+
+    // Okay this countmap's goal is to do the same as the above, except also do a level of expansion before counting
+    let final_rules_pass_count_map = 
+        calculate_final_rules_pass_count_map(
+            &last_rule_map, 
+            &semifinal_rules_pass_count_map,
+        );
+
+    Ok(PreCalculatedCaches {
+        first_pass_expansion: first_pass_rule_map,
+        final_pass_expansion_count: final_rules_pass_count_map,
+    })
+}
+
+pub struct Problem {
+    pub template: String,
+    pub rules: Vec<(char, char, char)>,
+}
+impl Problem {
+    /// The template on the first line, a blank line, then one rule per line.
+    pub fn parse(input: &str) -> Result<Self, Error> {
+        let mut lines = input.lines().enumerate().map(|(i, line)| (i + 1, line));
+
+        let (_, template) = lines.next().ok_or(Error::new(DAY, Cause::EmptyInput))?;
+        if template.is_empty() {
+            return Err(Error::new(DAY, Cause::EmptyInput));
+        }
+        all_consuming(alpha1::<_, ()>)(template)
+            .map_err(|_| Error::malformed(DAY, 1, template, "a polymer template"))?;
+
+        match lines.next() {
+            Some((_, "")) => (),
+            Some((i, line)) => return Err(Error::malformed(DAY, i, line, "a blank line")),
+            None => return Err(Error::at_line(DAY, 2, Cause::Malformed { content: String::new(), expected: "a blank line" })),
+        }
+
+        // editors and `aoc gen` like to leave blank lines at the end
+        let mut lines: Vec<_> = lines.collect();
+        while lines.last().is_some_and(|(_, line)| line.is_empty()) {
+            lines.pop();
+        }
+        let rules = lines.into_iter()
+            .map(|(i, line)| all_consuming(parse_triple)(line)
+                .map(|(_, rule)| rule)
+                .map_err(|_| Error::malformed(DAY, i, line, "a rule like \"AB -> C\"")))
+            .collect::<Result<Vec<_>, _>>()?;
+        if rules.is_empty() {
+            return Err(Error::at_line(DAY, 3, Cause::Malformed { content: String::new(), expected: "at least one rule" }));
+        }
+        Ok(Self {template: template.to_string(), rules})
+    }
+}
+
+fn parse_triple(input: &str) -> nom::IResult<&str, (char, char, char)> {
+    let (input, left) = anychar(input)?;
+    let (input, right) = anychar(input)?;
+    let (input, _) = tag(" -> ")(input)?;
+    let (input, end) = anychar(input)?;
+    Ok((input, (left, right, end)))
+}
+
+fn calculate_final_rules_pass_count_map(previous_rule: &Rule<Vec<char>>, previous_count: &Rule<Count>) -> Rule<Count> {
+    let mut final_rules_pass_count_map = BTreeMap::new();
+    for ((k0, k1), vs) in previous_rule {
+        let mut count_map = BTreeMap::new();
+
+        // For each pair of chars we add the sum
+        let full_expansion = sandwich(*k0, &vs[..], *k1);
+        for (first, second) in full_expansion.tuple_windows() {
+            if let Some(sub_expansion) = previous_count.get(&(first, second)) {
+                add_counts(&mut count_map, sub_expansion);
+            }
+            map_add(&mut count_map, second, 1);
+        }
+        // okay since we've doind BB -> ABA and DON'T want the bookends, but we've been adding the 2nd as we go, we gotta take it off
+        map_sub(&mut count_map, *k1, 1);
+        final_rules_pass_count_map.insert((*k0, *k1), count_map);
+    }
+    final_rules_pass_count_map
+}
+
+
+fn map_add<K: Ord>(map: &mut BTreeMap<K, u64>, k: K, n: u64) {
+    let count = map.entry(k).or_insert(0);
+    *count += n;
+}
+
+fn map_sub<K: Ord>(map: &mut BTreeMap<K, u64>, k: K, n: u64) {
+    let count = map.entry(k).or_insert(0);
+    *count -= n;
+}
+
+fn add_counts(this: &mut Count, other: &Count) {
+    for (v, v_count) in other {
+        map_add(this, *v, *v_count);
+    }
+}
+
+fn sandwich<'a, T: Clone>(start: T, middle: &'a [T], end: T) -> impl Iterator<Item=T> + 'a {
+    std::iter::once(start).chain(middle.iter().cloned()).chain(std::iter::once(end))
+}
+
+// Do an expansion, but instead of expanding out the text, use the pre-calculated expansion counts
+fn expand_with_counts(first: char, second: char, final_count_map: &Rule<Count>) -> Count {
+    let mut count = BTreeMap::from([(second, 1)]);
+
+    if let Some(expansion_count) = final_count_map.get(&(first, second)) {
+        add_counts(&mut count, expansion_count);
+    }
+    count
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc::{differential, Part, Solution};
+    use proptest::{collection::vec, option, sample::subsequence, strategy::Strategy};
+
+    // Actually build the polymer up one step at a time, only feasible for part 1
+    struct BruteForce;
+
+    impl Solution for BruteForce {
+        const DAY: u8 = DAY;
+        type Input = Problem;
+        type Part1 = u64;
+        type Part2 = u64;
+
+        fn parse(input: &str) -> Result<Self::Input, Error> {
+            Problem::parse(input)
+        }
+
+        fn part_1(problem: &Self::Input) -> Result<Self::Part1, Error> {
+            let rules: BTreeMap<_, _> = problem.rules.iter().map(|&(a, b, c)| ((a, b), c)).collect();
+            let mut polymer: Vec<char> = problem.template.chars().collect();
+            for _ in 0..10 {
+                let mut next = vec![polymer[0]];
+                for (&first, &second) in polymer.iter().tuple_windows() {
+                    if let Some(&inserted) = rules.get(&(first, second)) {
+                        next.push(inserted);
+                    }
+                    next.push(second);
+                }
+                polymer = next;
+            }
+            let mut counts = BTreeMap::new();
+            for c in polymer {
+                map_add(&mut counts, c, 1);
+            }
+            Ok(max_minus_min(counts.into_values()))
+        }
+
+        fn part_2(_problem: &Self::Input) -> Result<Self::Part2, Error> {
+            Err(Error::new(DAY, Cause::Unsupported("2^40 is a bit too long to brute force".to_string())))
+        }
+    }
+
+    /// A short template over a few letters, with rules for some (but not necessarily all) of the pairs.
+    fn problems() -> impl Strategy<Value = String> {
+        subsequence(vec!['B', 'C', 'H', 'N'], 1..=4).prop_flat_map(|alphabet| {
+            let letter = proptest::sample::select(alphabet.clone());
+            let pairs: Vec<(char, char)> = alphabet.iter()
+                .flat_map(|&a| alphabet.iter().map(move |&b| (a, b)))
+                .collect();
+            let rules = vec(option::of(letter.clone()), pairs.len());
+            (vec(letter, 1..=6), rules).prop_map(move |(template, rules)| {
+                let mut input: String = template.into_iter().collect();
+                input.push_str("\n\n");
+                for (&(a, b), c) in pairs.iter().zip(rules) {
+                    if let Some(c) = c {
+                        input.push_str(&format!("{}{} -> {}\n", a, b, c));
+                    }
+                }
+                input
+            })
+        })
+    }
+
+    #[test]
+    fn passes_that_dont_fit() -> Result<(), Error> {
+        let problem = Day14::parse(TEST)?;
+        for (first_pass, rules_passes) in [(0, 0), (0, 1), (1, 2), (3, 3), (5, 3)] {
+            let error = expand(&problem, first_pass, rules_passes).expect_err("the passes don't fit together");
+            assert!(matches!(error.cause, Cause::Unsupported(_)), "{:?}", error);
+        }
+        // 2^0 + 2^2 = 5 steps
+        assert_eq!(expand(&problem, 0, 2)?, 33);
+        Ok(())
+    }
+
+    #[test]
+    fn blank_lines_at_the_end() -> Result<(), Error> {
+        let problem = Day14::parse("NN\n\nNN -> C\n\n\n")?;
+        assert_eq!(problem.rules, [('N', 'N', 'C')]);
+        // but not in the middle of the rules
+        assert_eq!(Day14::parse("NN\n\nNN -> C\n\nNC -> B\n").err().and_then(|e| e.line), Some(4));
+        Ok(())
+    }
+
+    #[test]
+    fn pairs_without_rules_survive() -> Result<(), Error> {
+        // AB -> A keeps putting As in front of the B, and the AA pairs that makes have no rule but have to stay put
+        let problem = Day14::parse("AB\n\nAB -> A\n")?;
+        assert_eq!(BruteForce::part_1(&problem)?, 10);
+        assert_eq!(Day14::part_1(&problem)?, 10);
+        assert_eq!(Day14::part_2(&problem)?, 40);
+        Ok(())
+    }
+
+    #[test]
+    fn cached_expansion_matches_brute_force() {
+        differential::assert_agree::<BruteForce, Day14>(Some(Part::One), problems());
+    }
+}
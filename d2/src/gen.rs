@@ -0,0 +1,26 @@
+use aoc::{gen::Shape, Error};
+use rand::{Rng, RngCore};
+
+/// A course of `forward`, `down` and `up` commands, mostly going forwards and never flying the sub out of the water.
+pub fn generate(rng: &mut dyn RngCore, shape: &Shape) -> Result<String, Error> {
+    let size = shape.size.unwrap_or(1000);
+    let mut aim = 0;
+    let mut input = String::new();
+    for _ in 0..size {
+        let distance = rng.gen_range(1..=9);
+        let command = match rng.gen_range(0..4) {
+            0 | 1 => "forward",
+            2 => "down",
+            // the real inputs never aim above the surface so we don't either
+            _ if aim >= distance => "up",
+            _ => "down",
+        };
+        match command {
+            "down" => aim += distance,
+            "up" => aim -= distance,
+            _ => (),
+        }
+        input.push_str(&format!("{} {}\n", command, distance));
+    }
+    Ok(input)
+}
@@ -0,0 +1,139 @@
+use aoc::{Cause, Error};
+pub use navigate::{navigate, trace, Aimed, Navigator, Plain, State};
+use nom::character::complete::{alpha1, char, i32};
+
+pub mod gen;
+pub mod navigate;
+
+pub struct Day2;
+
+pub const INPUT: &str = include_str!("input.txt");
+pub const TEST: &str = include_str!("test.txt");
+
+const DAY: u8 = 2;
+
+impl aoc::Solution for Day2 {
+    const DAY: u8 = DAY;
+    /// Each command, along with the line it's on so that a command that can't be followed can say where it was.
+    type Input = Vec<(usize, Command)>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        let commands = input.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.is_empty())
+            .map(|(i, line)| match Command::parse_line(line) {
+                Ok(command) => Ok((i + 1, command)),
+                Err(cause) => Err(Error::at_line(Self::DAY, i + 1, cause)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if commands.is_empty() {
+            return Err(Error::new(Self::DAY, Cause::EmptyInput));
+        }
+        Ok(commands)
+    }
+
+    fn part_1(commands: &Self::Input) -> Result<Self::Part1, Error> {
+        navigate(Plain::default(), commands)?.product().map_err(|cause| Error::new(DAY, cause))
+    }
+
+    fn part_2(commands: &Self::Input) -> Result<Self::Part2, Error> {
+        navigate(Aimed::default(), commands)?.product().map_err(|cause| Error::new(DAY, cause))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Command {
+    Forward(i32),
+    Down(i32),
+    Up(i32),
+}
+impl Command {
+    fn parse(input: &str) -> nom::IResult<&str, (&str, i32)> {
+        let (input, command) = alpha1(input)?;
+        let (input, _) = char(' ')(input)?;
+        let (input, distance) = i32(input)?;
+        Ok((input, (command, distance)))
+    }
+
+    /// Parse a whole line, which has to be exactly one command.
+    pub fn parse_line(line: &str) -> Result<Self, Cause> {
+        let malformed = || Cause::Malformed { content: line.to_string(), expected: "a command and a distance" };
+        let (rest, (command, distance)) = Command::parse(line).map_err(|_| malformed())?;
+        if !rest.is_empty() {
+            return Err(malformed());
+        }
+        match command {
+            "forward" => Ok(Command::Forward(distance)),
+            "up" => Ok(Command::Up(distance)),
+            "down" => Ok(Command::Down(distance)),
+            _ => Err(Cause::UnknownCommand(command.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc::Solution;
+
+    #[test]
+    fn test_input() -> Result<(), Error> {
+        let commands = Day2::parse(TEST)?;
+        assert_eq!(Day2::part_1(&commands)?, 150);
+        assert_eq!(Day2::part_2(&commands)?, 900);
+        Ok(())
+    }
+
+    #[test]
+    fn models_from_outside() -> Result<(), Error> {
+        // a sub that can't go below 20, to check a model can be built on top of the ones here
+        struct Clamped(Aimed);
+        impl Navigator for Clamped {
+            fn apply(&mut self, command: Command) -> Result<(), Cause> {
+                self.0.apply(command)?;
+                self.0.depth = self.0.depth.min(20);
+                Ok(())
+            }
+            fn position(&self) -> i64 {
+                self.0.position
+            }
+            fn depth(&self) -> i64 {
+                self.0.depth
+            }
+        }
+        let commands = Day2::parse(TEST)?;
+        // the unclamped dive ends at 15 forward and 60 down
+        assert_eq!(navigate(Clamped(Aimed::default()), &commands)?.product(), Ok(15 * 20));
+        Ok(())
+    }
+
+    #[test]
+    fn traces_every_command() -> Result<(), Error> {
+        let commands = Day2::parse(TEST)?;
+        let states: Vec<State> = trace(Aimed::default(), &commands).collect::<Result<_, _>>()?;
+        assert_eq!(states.len(), commands.len());
+        assert_eq!(states[2], State { step: 2, position: 13, depth: 40, aim: Some(5) });
+        let last = *states.last().unwrap();
+        assert_eq!(last.position * last.depth, Day2::part_2(&commands)?);
+        assert_eq!(trace(Plain::default(), &commands).last().map(|state| state.map(|s| s.aim)), Some(Ok(None)));
+        Ok(())
+    }
+
+    #[test]
+    fn overflow_is_an_error() -> Result<(), Error> {
+        let max = i32::MAX;
+        let commands = Day2::parse(&format!("\ndown {max}\ndown {max}\ndown {max}\nforward {max}\nforward 1\n"))?;
+        let overflow = Error::at_line(DAY, 5, Cause::Overflow("the aim times the distance"));
+        assert_eq!(Day2::part_2(&commands), Err(overflow.clone()));
+        let states: Vec<_> = trace(Aimed::default(), &commands).collect();
+        assert_eq!(states.len(), 4);
+        assert_eq!(states[3], Err(overflow));
+
+        // every step fits but the answer doesn't
+        let commands = Day2::parse(&format!("forward {max}\nforward {max}\nforward {max}\ndown {max}\ndown {max}\n"))?;
+        assert_eq!(Day2::part_1(&commands), Err(Error::new(DAY, Cause::Overflow("the position times the depth"))));
+        Ok(())
+    }
+}
@@ -0,0 +1,133 @@
+//! The different ways a command can move the sub. The two the puzzle uses are here, and anything else that
+//! implements [`Navigator`] can be steered through a course the same way.
+
+use serde::Serialize;
+
+use aoc::{Cause, Error};
+
+use crate::{Command, DAY};
+
+/// A model of how the sub moves: where it's got to, and how each command changes that.
+///
+/// Positions and depths are `i64` since real inputs already come within a factor of 2 of overflowing an `i32`, but
+/// a course of big enough `i32`s can overflow even that, so everything is checked.
+pub trait Navigator {
+    /// Move the sub, or say what got too big if it can't be.
+    fn apply(&mut self, command: Command) -> Result<(), Cause>;
+    /// How far forward the sub has gone.
+    fn position(&self) -> i64;
+    /// How far down the sub is, which goes up as it dives.
+    fn depth(&self) -> i64;
+    /// Which way the sub's pointing, for models that have an aim at all.
+    fn aim(&self) -> Option<i64> {
+        None
+    }
+
+    /// The puzzle's answer: the position multiplied by the depth.
+    fn product(&self) -> Result<i64, Cause> {
+        self.position().checked_mul(self.depth()).ok_or(Cause::Overflow("the position times the depth"))
+    }
+}
+
+/// Steer through every command in `course`, which are each paired with the line they're on, starting from
+/// `navigator`, and return where it ends up.
+pub fn navigate<N: Navigator>(mut navigator: N, course: &[(usize, Command)]) -> Result<N, Error> {
+    for &(line, command) in course {
+        navigator.apply(command).map_err(|cause| Error::at_line(DAY, line, cause))?;
+    }
+    Ok(navigator)
+}
+
+/// Where the sub was after one command of a course.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct State {
+    /// The 0-based index of the command that was just followed.
+    pub step: usize,
+    pub position: i64,
+    pub depth: i64,
+    pub aim: Option<i64>,
+}
+
+/// Steer through `course` like [`navigate`], but yield where the sub is after every command. If a command can't be
+/// followed that's the last thing yielded.
+pub fn trace<'a, N: Navigator + 'a>(mut navigator: N, course: &'a [(usize, Command)]) -> impl Iterator<Item = Result<State, Error>> + 'a {
+    let mut failed = false;
+    course.iter().enumerate().map_while(move |(step, &(line, command))| {
+        if failed {
+            return None;
+        }
+        Some(match navigator.apply(command) {
+            Ok(()) => Ok(State { step, position: navigator.position(), depth: navigator.depth(), aim: navigator.aim() }),
+            Err(cause) => {
+                failed = true;
+                Err(Error::at_line(DAY, line, cause))
+            }
+        })
+    })
+}
+
+/// `a + b`, or what got too big if that overflows.
+fn add(a: i64, b: i64, what: &'static str) -> Result<i64, Cause> {
+    a.checked_add(b).ok_or(Cause::Overflow(what))
+}
+
+/// Part 1: `down` and `up` move the sub straight up and down.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Plain {
+    pub position: i64,
+    pub depth: i64,
+}
+
+impl Navigator for Plain {
+    fn apply(&mut self, command: Command) -> Result<(), Cause> {
+        match command {
+            Command::Forward(x) => self.position = add(self.position, x as i64, "the position")?,
+            Command::Down(x) => self.depth = add(self.depth, x as i64, "the depth")?,
+            Command::Up(x) => self.depth = add(self.depth, -(x as i64), "the depth")?,
+        }
+        Ok(())
+    }
+
+    fn position(&self) -> i64 {
+        self.position
+    }
+
+    fn depth(&self) -> i64 {
+        self.depth
+    }
+}
+
+/// Part 2: `down` and `up` tilt the sub, and going forward dives by however far it's tilted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Aimed {
+    pub position: i64,
+    pub depth: i64,
+    pub aim: i64,
+}
+
+impl Navigator for Aimed {
+    fn apply(&mut self, command: Command) -> Result<(), Cause> {
+        match command {
+            Command::Forward(x) => {
+                self.position = add(self.position, x as i64, "the position")?;
+                let dive = self.aim.checked_mul(x as i64).ok_or(Cause::Overflow("the aim times the distance"))?;
+                self.depth = add(self.depth, dive, "the depth")?;
+            }
+            Command::Down(x) => self.aim = add(self.aim, x as i64, "the aim")?,
+            Command::Up(x) => self.aim = add(self.aim, -(x as i64), "the aim")?,
+        }
+        Ok(())
+    }
+
+    fn position(&self) -> i64 {
+        self.position
+    }
+
+    fn depth(&self) -> i64 {
+        self.depth
+    }
+
+    fn aim(&self) -> Option<i64> {
+        Some(self.aim)
+    }
+}
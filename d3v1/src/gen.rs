@@ -0,0 +1,53 @@
+use aoc::{gen::Shape, Cause, Error, Solution};
+use rand::{seq::index, RngCore};
+
+use crate::Day3;
+
+/// How many random sets to try before giving up on finding a valid one.
+const ATTEMPTS: usize = 1000;
+
+/// A diagnostic report of distinct `width` bit readings.
+///
+/// Not every set of readings is a valid puzzle: the least common bit can be missing entirely, leaving no life support
+/// rating at all. So we keep drawing sets until both ratings come out to exactly one reading.
+pub fn generate(rng: &mut dyn RngCore, shape: &Shape) -> Result<String, Error> {
+    let size = shape.size.unwrap_or(1000);
+    let width = shape.width.unwrap_or(12);
+    let error = |reason: String| Error::new(Day3::DAY, Cause::Unsupported(reason));
+    if width == 0 || width > 32 {
+        return Err(error(format!("can't generate {} bit readings", width)));
+    }
+    if size as u64 > 1 << width {
+        return Err(error(format!("there aren't {} distinct {} bit readings", size, width)));
+    }
+
+    for _ in 0..ATTEMPTS {
+        let readings: Vec<u32> = index::sample(rng, 1 << width, size).into_iter().map(|r| r as u32).collect();
+        if rating(&readings, width, true).is_some() && rating(&readings, width, false).is_some() {
+            return Ok(format(&readings, width));
+        }
+    }
+    Err(error(format!("couldn't find {} readings of {} bits with a unique answer", size, width)))
+}
+
+/// Write `readings` out as a report, one `width` bit binary number per line.
+pub fn format<'a>(readings: impl IntoIterator<Item = &'a u32>, width: usize) -> String {
+    readings.into_iter().map(|r| format!("{:0width$b}\n", r, width = width)).collect()
+}
+
+// The slow and obvious version of the bit criteria, only used to check the generated readings make sense
+fn rating(readings: &[u32], width: usize, most_common: bool) -> Option<u32> {
+    let mut candidates = readings.to_vec();
+    for bit in (0..width).rev() {
+        if candidates.len() <= 1 {
+            break;
+        }
+        let ones = candidates.iter().filter(|&&c| c & (1 << bit) != 0).count();
+        let keep_ones = (ones * 2 >= candidates.len()) == most_common;
+        candidates.retain(|&c| (c & (1 << bit) != 0) == keep_ones);
+    }
+    match candidates[..] {
+        [rating] => Some(rating),
+        _ => None,
+    }
+}
@@ -0,0 +1,206 @@
+#![cfg_attr(feature = "nightly", feature(iter_partition_in_place))]
+use aoc::{Cause, Error};
+
+pub mod gen;
+
+pub struct Day3;
+
+pub const INPUT: &str = include_str!("input.txt");
+pub const TEST: &str = include_str!("test.txt");
+
+/// The diagnostic report: every reading is `width` bits wide.
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub width: usize,
+    pub readings: Vec<u32>,
+}
+
+impl aoc::Solution for Day3 {
+    const DAY: u8 = 3;
+    type Input = Report;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Report::parse(Self::DAY, input)
+    }
+
+    fn part_1(report: &Self::Input) -> Result<Self::Part1, Error> {
+        Ok(report.power_consumption())
+    }
+
+    fn part_2(report: &Self::Input) -> Result<Self::Part2, Error> {
+        let n = report.width - 1;
+        // A lone reading is already both ratings, partitioning it would leave one side empty
+        if let [reading] = report.readings[..] {
+            return Ok(reading as u64 * reading as u64);
+        }
+
+        // We need to do the first partition ourselves since the two answers are gotten from the two different partitions
+        let mut readings = report.readings.clone();
+        let (countg0, ones, zeroes) = step(n, readings.as_mut());
+        let (msbs, lsbs) = if countg0 { (ones, zeroes) } else { (zeroes, ones) };
+        let error = |cause| Error::new(Self::DAY, cause);
+        let oxygen = pick(true, n, msbs).map_err(error)?;
+        let co2 = pick(false, n, lsbs).map_err(error)?;
+        Ok(oxygen as u64 * co2 as u64)
+    }
+}
+
+impl Report {
+    /// Parse one binary number per line, all of them the same width as the first.
+    pub fn parse(day: u8, input: &str) -> Result<Self, Error> {
+        let width = aoc::bytes::lines(input.as_bytes()).next().map_or(0, |(_, l)| l.len());
+        if width == 0 {
+            return Err(Error::new(day, Cause::EmptyInput));
+        }
+        if width > 32 {
+            return Err(Error::new(day, Cause::Unsupported(format!("{} bit readings don't fit in a u32", width))));
+        }
+        let readings = aoc::bytes::lines(input.as_bytes())
+            .map(|(line, l)| match aoc::bytes::binary(l) {
+                Some(reading) if l.len() == width => Ok(reading),
+                _ => Err(Error::malformed(day, line, &String::from_utf8_lossy(l), "a binary number as wide as the first")),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Report { width, readings })
+    }
+
+    /// Part 1: the gamma rate is the most common bit in each place and the epsilon rate the least common, and the
+    /// power consumption is the two multiplied together. A tie counts as a one, the same as for the ratings.
+    pub fn power_consumption(&self) -> u64 {
+        let mut ones = vec![0; self.width];
+        for reading in &self.readings {
+            for (bit, count) in ones.iter_mut().enumerate() {
+                *count += (reading >> bit) as usize & 1;
+            }
+        }
+        let gamma = ones.iter()
+            .enumerate()
+            .filter(|&(_, &count)| count * 2 >= self.readings.len())
+            .fold(0u64, |gamma, (bit, _)| gamma | 1 << bit);
+        let epsilon = !gamma & ((1 << self.width) - 1);
+        gamma * epsilon
+    }
+}
+
+/// Bit `n` has just been used to narrow things down to `candidates`, carry on from the next bit down if need be.
+fn pick(msb: bool, n: usize, candidates: &mut [u32]) -> Result<u32, Cause> {
+    match candidates {
+        [] => Err(Cause::NoCandidates),
+        [x] => Ok(*x),
+        _ if n == 0 => Err(Cause::Ambiguous),
+        _ => go(msb, n - 1, candidates),
+    }
+}
+
+pub fn go(msb: bool, n: usize, xs: &mut [u32]) -> Result<u32, Cause> {
+    let (countg0, v1, v0) = step(n, xs);
+    let candidates = if countg0 == msb { v1 } else { v0 };
+    pick(msb, n, candidates)
+}
+
+pub fn step(n: usize, xs: &mut [u32]) -> (bool, &mut [u32], &mut [u32]) {
+    let xsn = xs.len();
+    #[cfg(feature = "nightly")]
+    let v1n = xs.iter_mut().partition_in_place(|&x| x & (1 << n) != 0);
+    #[cfg(not(feature = "nightly"))]
+    let v1n = partition_in_place(xs, |&x| x & (1 << n) != 0);
+    let (v1, v0) = xs.split_at_mut(v1n);
+    (v1n*2 >= xsn, v1, v0) // integer divisionnnnn!!!!
+}
+
+// Stable stand-in for `Iterator::partition_in_place`: everything matching `predicate` is moved to the front, in no
+// particular order, and the number of matches is returned.
+#[cfg(not(feature = "nightly"))]
+fn partition_in_place(xs: &mut [u32], predicate: impl Fn(&u32) -> bool) -> usize {
+    let (mut front, mut back) = (0, xs.len());
+    loop {
+        while front < back && predicate(&xs[front]) {
+            front += 1;
+        }
+        while front < back && !predicate(&xs[back - 1]) {
+            back -= 1;
+        }
+        if front == back {
+            return front;
+        }
+        xs.swap(front, back - 1);
+        front += 1;
+        back -= 1;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc::{differential, Solution};
+    use proptest::{collection::btree_set, strategy::Strategy};
+
+    // Straight from the puzzle text: filter the whole list down one bit at a time
+    struct Naive;
+
+    impl Solution for Naive {
+        const DAY: u8 = 3;
+        type Input = Report;
+        type Part1 = u64;
+        type Part2 = u64;
+
+        fn parse(input: &str) -> Result<Self::Input, Error> {
+            Report::parse(Self::DAY, input)
+        }
+
+        fn part_1(report: &Self::Input) -> Result<Self::Part1, Error> {
+            let (mut gamma, mut epsilon) = (0, 0);
+            for bit in (0..report.width).rev() {
+                let ones = report.readings.iter().filter(|&&r| r & (1 << bit) != 0).count();
+                let most_common = u64::from(ones * 2 >= report.readings.len());
+                gamma = gamma * 2 + most_common;
+                epsilon = epsilon * 2 + (1 - most_common);
+            }
+            Ok(gamma * epsilon)
+        }
+
+        fn part_2(report: &Self::Input) -> Result<Self::Part2, Error> {
+            let rating = |most_common: bool| {
+                let mut candidates = report.readings.clone();
+                for bit in (0..report.width).rev() {
+                    if candidates.len() <= 1 {
+                        break;
+                    }
+                    let ones = candidates.iter().filter(|&&c| c & (1 << bit) != 0).count();
+                    let keep_ones = (ones * 2 >= candidates.len()) == most_common;
+                    candidates.retain(|&c| (c & (1 << bit) != 0) == keep_ones);
+                }
+                match candidates[..] {
+                    [rating] => Ok(rating as u64),
+                    _ => Err(Error::new(Self::DAY, Cause::NoCandidates)),
+                }
+            };
+            Ok(rating(true)? * rating(false)?)
+        }
+    }
+
+    /// Distinct readings of up to `max_width` bits, including ones with no valid answer.
+    pub fn reports(max_width: usize) -> impl Strategy<Value = String> {
+        (1..=max_width).prop_flat_map(|width| {
+            btree_set(0..1u32 << width, 1..=(1 << width).min(64))
+                .prop_map(move |readings| crate::gen::format(&readings, width))
+        })
+    }
+
+    #[test]
+    fn step_go_matches_naive() {
+        differential::assert_agree::<Naive, Day3>(None, reports(10));
+    }
+
+    #[test]
+    #[cfg(not(feature = "nightly"))]
+    fn partition_in_place_splits_on_predicate() {
+        let mut xs = [5, 2, 8, 1, 9, 4];
+        let n = partition_in_place(&mut xs, |&x| x > 4);
+        assert_eq!(n, 3);
+        assert!(xs[..n].iter().all(|&x| x > 4));
+        assert!(xs[n..].iter().all(|&x| x <= 4));
+    }
+}
@@ -1,5 +1,5 @@
 // The depth of each semi-collapsed node
-const D: usize = 2;
+pub const D: usize = 2;
 // The number of elements that can be contained in that depth
 const L: usize = 1 << D;
 // a bitmask of `D` rightmost 1s
@@ -47,19 +47,20 @@ pub enum SemiCollapsedBinPrefixSet {
 }
 
 impl SemiCollapsedBinPrefixSet {
-    pub fn deconstruct(self) -> ([i32; L - 1], [Option<Node>; L]) {
+    // returns None if this isn't a Node
+    pub fn deconstruct(self) -> Option<([i32; L - 1], [Option<Node>; L])> {
         match self {
             SemiCollapsedBinPrefixSet::Node {
                 child_zeroes_minus_ones,
                 children,
-            } => (child_zeroes_minus_ones, children),
-            other => panic!("expected Node, found {:?}", other),
+            } => Some((child_zeroes_minus_ones, children)),
+            _ => None,
         }
     }
     // returns true if it was actually inserted
     // only cares about the right-most `i+1` bits
     pub fn insert(&mut self, x: u32, i: usize) -> bool {
-        debug_assert!(i % D == 0);
+        debug_assert!(i.is_multiple_of(D));
 
         // I don't like the double match here but what can you do
         if let SemiCollapsedBinPrefixSet::Empty = self {
@@ -71,18 +72,7 @@ impl SemiCollapsedBinPrefixSet {
             };
         }
 
-        let child_index = (x as usize >> (i as usize - D)) & D_MASK;
-        println!("PREFIX");
-        println!("x = {:b}", x);
-        dbg!(i);
-        println!("x>>i-D = {:b}", x as usize >> (i as usize - D));
-        println!("M = {:b}", D_MASK);
-        println!(
-            "x>>i-D & M = {:b}",
-            (x as usize >> (i as usize - D)) & D_MASK
-        );
-        println!("ix {} {:b}", child_index, child_index);
-        println!("");
+        let child_index = (x as usize >> (i - D)) & D_MASK;
 
         if i == D {
             /*
@@ -153,8 +143,6 @@ impl SemiCollapsedBinPrefixSet {
                             for j in 0..D {
                                 let mask = 1 << (D - j - 1);
                                 let jth_leftmost_bit_set = x & mask != 0;
-                                println!("MINUS B");
-                                dbg!(&child_zeroes_minus_ones);
                                 let offset = if jth_leftmost_bit_set {
                                     child_zeroes_minus_ones[ix] -= 1;
                                     mask
@@ -163,14 +151,6 @@ impl SemiCollapsedBinPrefixSet {
                                     1
                                 };
                                 ix += offset as usize;
-                                dbg!(D);
-                                dbg!(j);
-                                println!("x  = {:b}", x);
-                                println!("m  = {:b}", mask);
-                                dbg!(jth_leftmost_bit_set);
-                                println!("o  = {:b}", offset);
-                                println!("ix = {:b}", ix);
-                                println!("");
                             }
                             true
                         }
@@ -206,8 +186,6 @@ impl SemiCollapsedBinPrefixSet {
                             for j in 0..D {
                                 let mask = 1 << (D - j - 1);
                                 let jth_leftmost_bit_set = x & mask != 0;
-                                println!("MINUS RB");
-                                dbg!(&child_zeroes_minus_ones);
                                 let offset = if jth_leftmost_bit_set {
                                     child_zeroes_minus_ones[ix] -= 1;
                                     mask
@@ -216,14 +194,6 @@ impl SemiCollapsedBinPrefixSet {
                                     1
                                 };
                                 ix += offset as usize;
-                                dbg!(D);
-                                dbg!(j);
-                                println!("x  = {:b}", x);
-                                println!("m  = {:b}", mask);
-                                dbg!(jth_leftmost_bit_set);
-                                println!("o  = {:b}", offset);
-                                println!("ix = {:b}", ix);
-                                println!("");
                             }
 
                             true
@@ -237,8 +207,6 @@ impl SemiCollapsedBinPrefixSet {
                                 for j in 0..D {
                                     let mask = 1 << (D - j - 1);
                                     let jth_leftmost_bit_set = x & mask != 0;
-                                    println!("MINUS RR");
-                                    dbg!(&child_zeroes_minus_ones);
                                     let offset = if jth_leftmost_bit_set {
                                         child_zeroes_minus_ones[ix] -= 1;
                                         mask
@@ -247,17 +215,9 @@ impl SemiCollapsedBinPrefixSet {
                                         1
                                     };
                                     ix += offset as usize;
-                                    dbg!(D);
-                                    dbg!(j);
-                                    println!("x  = {:b}", x);
-                                    println!("m  = {:b}", mask);
-                                    dbg!(jth_leftmost_bit_set);
-                                    println!("o  = {:b}", offset);
-                                    println!("ix = {:b}", ix);
-                                    println!("");
                                 }
                             }
-                            return inserted;
+                            inserted
                         }
                     }
                 }
@@ -321,7 +281,7 @@ mod test {
         x.insert(0b0001, 4);
         assert!(x.sole_leaf().is_none());
 
-        let (counts, children) = x.deconstruct();
+        let (counts, children) = x.deconstruct().ok_or("not a node")?;
         let [child_00, child_01, child_10, child_11] = children;
         assert_eq!(counts, [3, -1, 0]);
         assert!(child_10.is_none());
@@ -330,7 +290,7 @@ mod test {
         let node_00 = child_00.ok_or("none")?;
         assert_eq!(node_00.child_count, 1);
         assert_eq!(node_00.child.sole_leaf().ok_or("none")?, 0b0001);
-        let (counts_00, children_00) = node_00.child.deconstruct();
+        let (counts_00, children_00) = node_00.child.deconstruct().ok_or("not a node")?;
         assert_eq!(counts_00, [1, -1, 0]);
         let [child_0000, child_0001, child_0010, child_0011] = children_00;
         assert!(child_0000.is_none());
@@ -344,7 +304,7 @@ mod test {
         assert_eq!(node_01.child_count, 2);
         assert!(node_01.child.sole_leaf().is_none());
 
-        let (counts_01, children_01) = node_01.child.deconstruct();
+        let (counts_01, children_01) = node_01.child.deconstruct().ok_or("not a node")?;
         assert_eq!(counts_01, [2, 0, 0]);
         let [child_0100, child_0101, child_0110, child_0111] = children_01;
         assert!(child_0110.is_none());
@@ -0,0 +1,122 @@
+use aoc::{Cause, Error};
+pub use d3v1::Report;
+
+mod bin_prefix_set;
+pub use bin_prefix_set::{Node, SemiCollapsedBinPrefixSet, D};
+
+pub struct Day3;
+
+pub const INPUT: &str = include_str!("input.txt");
+pub const TEST: &str = include_str!("test.txt");
+
+// IMPORTANT NOTE: this doesn't actually quite work yet, just needs some debugging to find the certain off by one error
+impl aoc::Solution for Day3 {
+    const DAY: u8 = 3;
+    type Input = Report;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        let report = Report::parse(Self::DAY, input)?;
+        // n.b. assuming this isn't 1 level deep
+        if !report.width.is_multiple_of(D) || report.width <= D {
+            let reason = format!("{} bit readings, only multiples of {} up to 32 bits work", report.width, D);
+            return Err(Error::new(Self::DAY, Cause::Unsupported(reason)));
+        }
+        Ok(report)
+    }
+
+    fn part_1(report: &Self::Input) -> Result<Self::Part1, Error> {
+        // nothing to do with the prefix set, that's only for the ratings
+        Ok(report.power_consumption())
+    }
+
+    fn part_2(report: &Self::Input) -> Result<Self::Part2, Error> {
+        let error = |cause| Error::new(Self::DAY, cause);
+        let mut tree = SemiCollapsedBinPrefixSet::Empty;
+        for &l in &report.readings {
+            tree.insert(l, report.width);
+        }
+
+        // We need to do the first partition ourselves since the two answers are gotten from the two different partitions
+        // FIXME: this is fixed for D = 2 and not sufficiently general yet
+        let (counts, children) = tree.deconstruct().ok_or_else(|| error(Cause::NoCandidates))?;
+        let [children_00, children_01, children_10, children_11] = children;
+        let (msbs, lsbs) = if counts[0] >= 0 {
+            if counts[1] >= 0 {
+                (children_00, children_11)
+            } else {
+                (children_01, children_10)
+            }
+        } else {
+            if counts[2] >= 0 {
+                (children_10, children_01)
+            } else {
+                (children_11, children_00)
+            }
+        };
+        let msbs = *msbs.ok_or_else(|| error(Cause::NoCandidates))?.child;
+        let lsbs = *lsbs.ok_or_else(|| error(Cause::NoCandidates))?.child;
+        let oxygen = go(true, msbs).map_err(error)?;
+        let co2 = go(false, lsbs).map_err(error)?;
+        Ok(oxygen as u64 * co2 as u64)
+    }
+}
+
+// FIXME: this is fixed for D = 2 and not sufficiently general yet
+pub fn go(msb: bool, xs: SemiCollapsedBinPrefixSet) -> Result<u32, Cause> {
+    let (counts, children) = xs.deconstruct().ok_or(Cause::NoCandidates)?;
+    let [children_00, children_01, children_10, children_11] = children;
+    let keep_zero = if counts[0] == 0 {
+        !msb
+    } else {
+        (counts[0] > 0) == msb
+    };
+    let candidates = if keep_zero {
+        let keep_zero = if counts[1] == 0 {
+            !msb
+        } else {
+            (counts[1] > 0) == msb
+        };
+        if keep_zero {
+            children_00
+        } else {
+            children_01
+        }
+    } else {
+        let keep_zero = if counts[2] == 0 {
+            !msb
+        } else {
+            (counts[2] > 0) == msb
+        };
+        if keep_zero {
+            children_10
+        } else {
+            children_11
+        }
+    }.ok_or(Cause::NoCandidates)?;
+
+    // FIXME: add sole_leaf method to `Node` too, doesn't make sense to throw away the count here
+    match candidates.child.sole_leaf() {
+        Some(leaf) => Ok(leaf),
+        None => go(msb, *candidates.child),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc::differential;
+    use proptest::{collection::btree_set, strategy::Strategy};
+
+    #[test]
+    #[ignore = "the off by one in the prefix set still needs finding, run with --ignored to get a minimal input"]
+    fn prefix_set_matches_d3v1() {
+        // only even widths since that's all this handles so far
+        let reports = (2..=5usize).prop_map(|half| half * D).prop_flat_map(|width| {
+            btree_set(0..1u32 << width, 1..=64)
+                .prop_map(move |readings| d3v1::gen::format(&readings, width))
+        });
+        differential::assert_agree::<d3v1::Day3, Day3>(None, reports);
+    }
+}
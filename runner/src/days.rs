@@ -0,0 +1,36 @@
+use aoc::{gen::Generator, Solution, Solver, Timer};
+
+/// A day that the runner knows how to solve.
+pub struct Day {
+    /// What the day is called on the command line, e.g. `14` or `3v2`.
+    pub name: &'static str,
+    /// The bundled puzzle input, used when no `--input` is given.
+    pub input: &'static str,
+    /// The worked example from the puzzle text.
+    pub test: &'static str,
+    pub solve: Solver,
+    pub time: Timer,
+    /// Writes random inputs of the same kind as `input`.
+    pub generate: Generator,
+}
+
+impl Day {
+    pub const fn new<S: Solution>(name: &'static str, input: &'static str, test: &'static str, generate: Generator) -> Self {
+        Day { name, input, test, solve: aoc::solve::<S>, time: aoc::time::<S>, generate }
+    }
+}
+
+pub const DAYS: &[Day] = &[
+    Day::new::<d1::Day1>("1", d1::INPUT, d1::TEST, d1::gen::generate),
+    Day::new::<d2::Day2>("2", d2::INPUT, d2::TEST, d2::gen::generate),
+    Day::new::<d3v1::Day3>("3v1", d3v1::INPUT, d3v1::TEST, d3v1::gen::generate),
+    // both versions of day 3 solve the same puzzle so they can share a generator
+    Day::new::<d3v2::Day3>("3v2", d3v2::INPUT, d3v2::TEST, d3v1::gen::generate),
+    Day::new::<d14::Day14>("14", d14::INPUT, d14::TEST, d14::gen::generate),
+];
+
+/// Look up a day by name, accepting an optional leading `d` so that crate names work too.
+pub fn find(name: &str) -> Option<&'static Day> {
+    let name = name.strip_prefix('d').unwrap_or(name);
+    DAYS.iter().find(|day| day.name == name)
+}
@@ -0,0 +1,78 @@
+use std::{error::Error, io::{self, BufWriter, Write}};
+
+use aoc::Solution;
+use clap::ValueEnum;
+use d2::{Day2, Navigator, State};
+
+/// Which of d2's navigators to steer with.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Model {
+    /// `down` and `up` move the sub straight up and down, as in part 1
+    Plain,
+    /// `down` and `up` change the aim, as in part 2
+    Aimed,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum TraceFormat {
+    /// With a header row, and the aim left empty for models without one
+    Csv,
+    /// One JSON object per line, with a null aim for models without one
+    Jsonl,
+}
+
+/// Steer through the course in `input` and print where the sub ends up, or with `trace` where it was after every
+/// command.
+pub fn run(input: &str, model: Model, trace: Option<TraceFormat>) -> Result<(), Box<dyn Error>> {
+    let course = Day2::parse(input)?;
+    match (model, trace) {
+        (Model::Plain, None) => print(d2::navigate(d2::Plain::default(), &course)?)?,
+        (Model::Aimed, None) => print(d2::navigate(d2::Aimed::default(), &course)?)?,
+        (Model::Plain, Some(format)) => export(d2::trace(d2::Plain::default(), &course), format)?,
+        (Model::Aimed, Some(format)) => export(d2::trace(d2::Aimed::default(), &course), format)?,
+    }
+    Ok(())
+}
+
+fn print(navigator: impl Navigator) -> Result<(), aoc::Error> {
+    println!("position: {}", navigator.position());
+    println!("depth: {}", navigator.depth());
+    let product = navigator.product().map_err(|cause| aoc::Error::new(Day2::DAY, cause))?;
+    println!("product: {}", product);
+    Ok(())
+}
+
+fn export(states: impl Iterator<Item = Result<State, aoc::Error>>, format: TraceFormat) -> Result<(), Box<dyn Error>> {
+    // buffered since there's a line per command, and real courses are a thousand commands long
+    let mut out = BufWriter::new(io::stdout().lock());
+    match write_trace(&mut out, states, format) {
+        // whatever we're piped into has seen enough, e.g. `head`, which is a fine way for the trace to end
+        Err(e) if e.downcast_ref::<io::Error>().is_some_and(|e| e.kind() == io::ErrorKind::BrokenPipe) => Ok(()),
+        result => result,
+    }
+}
+
+fn write_trace(
+    out: &mut impl Write,
+    states: impl Iterator<Item = Result<State, aoc::Error>>,
+    format: TraceFormat,
+) -> Result<(), Box<dyn Error>> {
+    if let TraceFormat::Csv = format {
+        writeln!(out, "step,position,depth,aim")?;
+    }
+    for state in states {
+        let state = state?;
+        match format {
+            TraceFormat::Csv => {
+                let aim = state.aim.map_or(String::new(), |aim| aim.to_string());
+                writeln!(out, "{},{},{},{}", state.step, state.position, state.depth, aim)?;
+            }
+            TraceFormat::Jsonl => {
+                // to a string first so that every error writing it out is an `io::Error`
+                writeln!(out, "{}", serde_json::to_string(&state)?)?;
+            }
+        }
+    }
+    out.flush()?;
+    Ok(())
+}
@@ -0,0 +1,290 @@
+use std::{error::Error, fs::{self, File}, io::{self, BufRead, BufReader, Read}, num::NonZeroUsize, path::{Path, PathBuf}, process};
+
+use aoc::{gen::{self, Shape}, Part};
+use clap::{Parser, Subcommand, ValueEnum};
+use dive::{Model, TraceFormat};
+use sonar::{ReportFormat, Stat};
+
+mod days;
+mod dive;
+mod scaffold;
+mod sonar;
+mod timing;
+mod verify;
+
+#[derive(Parser)]
+#[command(about = "Run the Advent of Code solutions in this workspace")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve a single day
+    Run {
+        /// Which day to run, e.g. `1`, `3v2` or `14`
+        day: String,
+        /// Only solve this part
+        #[arg(long, value_enum)]
+        part: Option<PartArg>,
+        /// Read the puzzle input from this file, or `-` for stdin. Defaults to the day's bundled input.
+        #[arg(long)]
+        input: Option<PathBuf>,
+    },
+    /// Check every day's answers on its bundled inputs against `answers.toml`
+    Verify {
+        /// Only verify this day
+        day: Option<String>,
+    },
+    /// Time parsing and each part separately, over repeated runs on the bundled inputs
+    Time {
+        /// Only time this day
+        day: Option<String>,
+        /// How many times to run each day
+        #[arg(long, default_value_t = 10)]
+        runs: usize,
+        /// Also write every statistic to this file as JSON
+        #[arg(long)]
+        json: Option<PathBuf>,
+    },
+    /// Write a random input for a day, to stdout unless `--output` is given
+    Gen {
+        day: String,
+        /// Generating again with the same seed gives the same input. Picked at random and printed if not given.
+        #[arg(long)]
+        seed: Option<u64>,
+        /// How many lines to write, or for day 14 how long the template is
+        #[arg(long)]
+        size: Option<usize>,
+        /// How many bits wide day 3's readings are
+        #[arg(long)]
+        width: Option<usize>,
+        /// Which letters day 14's polymers are made of
+        #[arg(long)]
+        alphabet: Option<String>,
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Count how many times the sum of a sliding window of day 1's depths increases, for any size of window
+    Sonar {
+        /// How many depths to add up in each window: 1 is part 1 and 3 is part 2
+        #[arg(long, default_value = "1")]
+        window: NonZeroUsize,
+        /// Read the depths from this file, or `-` for stdin. Defaults to day 1's bundled input, or stdin with `--stream`.
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// Memory-map `--input` rather than reading it in, for sweeps too big to copy. The file mustn't be changed
+        /// until we're done with it: if it's truncated we'll be killed by SIGBUS. Anything that isn't a regular file
+        /// is read in as usual.
+        #[arg(long, requires = "input", conflicts_with = "stream")]
+        mmap: bool,
+        /// Read the depths a line at a time and print each increase as soon as it's seen. Lines that aren't depths
+        /// are skipped.
+        #[arg(long, conflicts_with = "lenient")]
+        stream: bool,
+        /// Carry on past lines that aren't depths rather than failing, by doing this with them
+        #[arg(long, value_enum)]
+        lenient: Option<LenientArg>,
+        /// Rather than just the count, report where the increases are and how the depths rise and fall. With
+        /// `--lenient break` there's one report for each unbroken stretch of depths.
+        #[arg(long, value_enum, conflicts_with = "stream")]
+        report: Option<ReportFormat>,
+        /// Rather than counting increases, print this for every window, one per line
+        #[arg(long, value_enum, conflicts_with_all = ["stream", "report"])]
+        stat: Option<Stat>,
+        /// What kind of number the depths are
+        #[arg(long = "type", value_enum, default_value = "u32")]
+        depth_type: DepthType,
+        /// Count the increases on every core at once
+        #[arg(long, conflicts_with_all = ["stream", "report", "stat"])]
+        parallel: bool,
+    },
+    /// Steer through day 2's course with either model of how the sub moves, and show where it ends up or how it got
+    /// there
+    Dive {
+        #[arg(long, value_enum, default_value = "aimed")]
+        model: Model,
+        /// Read the course from this file, or `-` for stdin. Defaults to day 2's bundled input.
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// Rather than just where the sub ends up, write out its position, depth and aim after every command
+        #[arg(long, value_enum)]
+        trace: Option<TraceFormat>,
+    },
+    /// Start a new day: create its crate and register it with the workspace, the runner and `answers.toml`
+    New {
+        day: u8,
+        /// The root of the workspace to add the day to
+        #[arg(long, default_value = concat!(env!("CARGO_MANIFEST_DIR"), "/.."))]
+        root: PathBuf,
+    },
+    /// List the days that can be run
+    List,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum PartArg {
+    #[value(name = "1")]
+    One,
+    #[value(name = "2")]
+    Two,
+}
+
+impl From<PartArg> for Part {
+    fn from(part: PartArg) -> Self {
+        match part {
+            PartArg::One => Part::One,
+            PartArg::Two => Part::Two,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum LenientArg {
+    /// Leave them out
+    Skip,
+    /// Leave them out and don't let any window span the gap
+    Break,
+    /// Fill them in from the depths either side
+    Interpolate,
+}
+
+impl From<LenientArg> for d1::Mode {
+    fn from(lenient: LenientArg) -> Self {
+        match lenient {
+            LenientArg::Skip => d1::Mode::Skip,
+            LenientArg::Break => d1::Mode::Break,
+            LenientArg::Interpolate => d1::Mode::Interpolate,
+        }
+    }
+}
+
+/// What kind of number the depths are.
+#[derive(Clone, Copy, ValueEnum)]
+enum DepthType {
+    U32,
+    I64,
+    U64,
+    F64,
+}
+
+fn main() {
+    // Print errors with `Display` rather than the `Debug` that returning them from `main` would give
+    if let Err(e) = run(Cli::parse().command) {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    }
+}
+
+fn run(command: Command) -> Result<(), Box<dyn Error>> {
+    match command {
+        Command::Run { day, part, input } => {
+            let day = days::find(&day).ok_or_else(|| format!("unknown day {:?}", day))?;
+            let input = match input {
+                Some(path) => read_input(&path)?,
+                None => day.input.to_string(),
+            };
+            print!("{}", (day.solve)(&input, part.map(Part::from))?);
+        }
+        Command::Verify { day } => {
+            let failures = verify::run(select(day)?)?;
+            if failures > 0 {
+                return Err(format!("{} checks failed", failures).into());
+            }
+        }
+        Command::Time { day, runs, json } => {
+            timing::run(select(day)?, runs, json.as_deref())?;
+        }
+        Command::Gen { day, seed, size, width, alphabet, output } => {
+            let day = days::find(&day).ok_or_else(|| format!("unknown day {:?}", day))?;
+            let seed = seed.unwrap_or_else(|| {
+                let seed = rand::random();
+                eprintln!("seed: {}", seed);
+                seed
+            });
+            let input = gen::generate(day.generate, seed, &Shape { size, width, alphabet })?;
+            match output {
+                Some(path) => fs::write(path, input)?,
+                None => print!("{}", input),
+            }
+        }
+        Command::Sonar { window, input, stream: true, depth_type, .. } => {
+            let reader: Box<dyn BufRead> = match input {
+                Some(path) if path.as_os_str() != "-" => Box::new(BufReader::new(File::open(path)?)),
+                _ => Box::new(io::stdin().lock()),
+            };
+            let stream = match depth_type {
+                DepthType::U32 => sonar::stream::<u32>,
+                DepthType::I64 => sonar::stream::<i64>,
+                DepthType::U64 => sonar::stream::<u64>,
+                DepthType::F64 => sonar::stream::<f64>,
+            };
+            stream(reader, window)?;
+        }
+        Command::Sonar { window, input, mmap, stream: false, lenient, report, stat, depth_type, parallel } => {
+            let mapped;
+            let read;
+            let input: &[u8] = match input {
+                // SAFETY: the caller asked for this with `--mmap`, whose docs say the file mustn't change while we run
+                Some(path) if mmap && fs::metadata(&path)?.is_file() => {
+                    mapped = unsafe { aoc::bytes::map(&path)? };
+                    &mapped
+                }
+                Some(path) if path.as_os_str() == "-" => {
+                    read = read_input(&path)?.into_bytes();
+                    &read
+                }
+                // not necessarily UTF-8, that's up to the depth parser to complain about
+                Some(path) => {
+                    read = fs::read(path)?;
+                    &read
+                }
+                None => d1::INPUT.as_bytes(),
+            };
+            let analyse = match depth_type {
+                DepthType::U32 => sonar::analyse::<u32>,
+                DepthType::I64 => sonar::analyse::<i64>,
+                DepthType::U64 => sonar::analyse::<u64>,
+                DepthType::F64 => sonar::analyse::<f64>,
+            };
+            analyse(input, window, lenient.map_or(d1::Mode::Strict, d1::Mode::from), report, stat, parallel)?;
+        }
+        Command::Dive { model, input, trace } => {
+            let input = match input {
+                Some(path) => read_input(&path)?,
+                None => d2::INPUT.to_string(),
+            };
+            dive::run(&input, model, trace)?;
+        }
+        Command::New { day, root } => {
+            scaffold::new_day(&root, day)?;
+            println!("created d{}, fill in src/test.txt, src/input.txt and answers.toml", day);
+        }
+        Command::List => {
+            for day in days::DAYS {
+                println!("{}", day.name);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Either the one named day, or all of them.
+fn select(day: Option<String>) -> Result<Vec<&'static days::Day>, String> {
+    match day {
+        Some(day) => Ok(vec![days::find(&day).ok_or_else(|| format!("unknown day {:?}", day))?]),
+        None => Ok(days::DAYS.iter().collect()),
+    }
+}
+
+/// Read the whole input from a file, treating `-` as stdin.
+fn read_input(path: &Path) -> io::Result<String> {
+    if path.as_os_str() == "-" {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input)?;
+        Ok(input)
+    } else {
+        fs::read_to_string(path)
+    }
+}
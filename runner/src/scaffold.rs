@@ -0,0 +1,154 @@
+use std::{error::Error, fs, path::Path};
+
+const CARGO_TOML: &str = r#"[package]
+name = "d{day}"
+version = "0.1.0"
+authors = ["David McGillicuddy <contact@djmcgill.co.uk>"]
+edition = "2021"
+
+# See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html
+
+[dependencies]
+aoc = { path = "../aoc" }
+rand = "0.8"
+"#;
+
+const LIB_RS: &str = r#"use aoc::{Error, Unsolved};
+
+pub mod gen;
+
+pub struct Day{day};
+
+pub const INPUT: &str = include_str!("input.txt");
+pub const TEST: &str = include_str!("test.txt");
+
+impl aoc::Solution for Day{day} {
+    const DAY: u8 = {day};
+    type Input = Vec<String>;
+    type Part1 = Unsolved;
+    type Part2 = Unsolved;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(input.lines().map(str::to_string).collect())
+    }
+
+    fn part_1(_input: &Self::Input) -> Result<Self::Part1, Error> {
+        Ok(Unsolved)
+    }
+
+    fn part_2(_input: &Self::Input) -> Result<Self::Part2, Error> {
+        Ok(Unsolved)
+    }
+}
+"#;
+
+const GEN_RS: &str = r#"use aoc::{gen::Shape, Cause, Error};
+use rand::RngCore;
+
+pub fn generate(_rng: &mut dyn RngCore, _shape: &Shape) -> Result<String, Error> {
+    Err(Error::new({day}, Cause::Unsupported("there's no generator for day {day} yet".to_string())))
+}
+"#;
+
+const MAIN_RS: &str = r#"use d{day}::{Day{day}, INPUT};
+
+fn main() -> Result<(), aoc::Error> {
+    aoc::run::<Day{day}>(INPUT)
+}
+"#;
+
+/// Create a `d{day}` crate in the workspace at `root`, and register it everywhere the runner needs to know about it.
+pub fn new_day(root: &Path, day: u8) -> Result<(), Box<dyn Error>> {
+    let name = format!("d{}", day);
+    let dir = root.join(&name);
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()).into());
+    }
+    let fill = |template: &str| template.replace("{day}", &day.to_string());
+
+    fs::create_dir_all(dir.join("src"))?;
+    fs::write(dir.join("Cargo.toml"), fill(CARGO_TOML))?;
+    fs::write(dir.join("src/lib.rs"), fill(LIB_RS))?;
+    fs::write(dir.join("src/gen.rs"), fill(GEN_RS))?;
+    fs::write(dir.join("src/main.rs"), fill(MAIN_RS))?;
+    fs::write(dir.join("src/input.txt"), "")?;
+    fs::write(dir.join("src/test.txt"), "")?;
+
+    insert_line(&root.join("Cargo.toml"), "members = [", "]", day, &format!("    \"{}\",", name))?;
+    insert_line(&root.join("runner/Cargo.toml"), "[dependencies]", "", day, &format!("{} = {{ path = \"../{}\" }}", name, name))?;
+    insert_line(
+        &root.join("runner/src/days.rs"),
+        "pub const DAYS",
+        "];",
+        day,
+        &format!("    Day::new::<{0}::Day{1}>(\"{1}\", {0}::INPUT, {0}::TEST, {0}::gen::generate),", name, day),
+    )?;
+
+    // no answers yet, but the empty tables are there ready to fill in
+    let manifest = root.join("answers.toml");
+    let mut answers = fs::read_to_string(&manifest)?;
+    answers.push_str(&format!("\n[\"{0}\".test]\n\n[\"{0}\".input]\n", day));
+    fs::write(manifest, answers)?;
+    Ok(())
+}
+
+/// Insert `line` into the block of lines that starts after `start` and ends at `end`, keeping the days in the block in
+/// order.
+fn insert_line(path: &Path, start: &str, end: &str, day: u8, line: &str) -> Result<(), Box<dyn Error>> {
+    let contents = fs::read_to_string(path)?;
+    let mut lines: Vec<&str> = contents.lines().collect();
+    let block_start = lines.iter().position(|l| l.starts_with(start))
+        .ok_or_else(|| format!("couldn't find {:?} in {}", start, path.display()))? + 1;
+    let block_end = lines[block_start..].iter().position(|l| l.trim() == end)
+        .map_or(lines.len(), |i| block_start + i);
+
+    let block = &lines[block_start..block_end];
+    let position = match block.iter().position(|l| day_number(l).is_some_and(|d| d > day as u32)) {
+        Some(i) => block_start + i,
+        // after the last day, so that anything else at the end of the block stays there
+        None => block.iter().rposition(|l| day_number(l).is_some()).map_or(block_end, |i| block_start + i + 1),
+    };
+    lines.insert(position, line);
+    fs::write(path, lines.join("\n") + "\n")?;
+    Ok(())
+}
+
+/// The number of the first day crate mentioned in `line`, like the 14 in `d14 = { path = "../d14" }`.
+fn day_number(line: &str) -> Option<u32> {
+    let bytes = line.as_bytes();
+    (0..bytes.len())
+        .filter(|&i| bytes[i] == b'd' && (i == 0 || !bytes[i - 1].is_ascii_alphanumeric()))
+        .find_map(|i| {
+            let digits: String = line[i + 1..].chars().take_while(char::is_ascii_digit).collect();
+            digits.parse().ok()
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn registers_new_day_in_order() -> Result<(), Box<dyn Error>> {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        fs::create_dir_all(root.join("runner/src"))?;
+        fs::write(root.join("Cargo.toml"), "[workspace]\nmembers = [\n    \"aoc\",\n    \"d1\",\n    \"d14\",\n    \"runner\",\n]\n")?;
+        fs::write(root.join("runner/Cargo.toml"), "[dependencies]\naoc = { path = \"../aoc\" }\nd1 = { path = \"../d1\" }\nd14 = { path = \"../d14\" }\nrand = \"0.8\"\n")?;
+        fs::write(root.join("runner/src/days.rs"), "pub const DAYS: &[Day] = &[\n    Day::new::<d1::Day1>(\"1\", d1::INPUT, d1::TEST, d1::gen::generate),\n];\n")?;
+        fs::write(root.join("answers.toml"), "")?;
+
+        new_day(&root, 7)?;
+
+        let members = fs::read_to_string(root.join("Cargo.toml"))?;
+        assert!(members.contains("    \"d1\",\n    \"d7\",\n    \"d14\",\n"));
+        let dependencies = fs::read_to_string(root.join("runner/Cargo.toml"))?;
+        assert!(dependencies.contains("d1 = { path = \"../d1\" }\nd7 = { path = \"../d7\" }\nd14"));
+        let days = fs::read_to_string(root.join("runner/src/days.rs"))?;
+        assert!(days.contains("d1::gen::generate),\n    Day::new::<d7::Day7>(\"7\", d7::INPUT, d7::TEST, d7::gen::generate),\n];"));
+        assert!(fs::read_to_string(root.join("d7/src/lib.rs"))?.contains("impl aoc::Solution for Day7 {"));
+        assert!(new_day(&root, 7).is_err());
+
+        fs::remove_dir_all(root)?;
+        Ok(())
+    }
+}
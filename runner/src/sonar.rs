@@ -0,0 +1,97 @@
+use std::{error::Error, fmt::Debug, io::BufRead, num::NonZeroUsize};
+
+use clap::ValueEnum;
+use d1::{rolling::RollingExt, Depth, Mode};
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ReportFormat {
+    Table,
+    /// An array with a report for each stretch of depths
+    Json,
+}
+
+/// Something about each window to print, rather than counting increases.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Stat {
+    Sum,
+    Mean,
+    Min,
+    Max,
+    /// Halfway between the middle two for even sized windows
+    Median,
+}
+
+/// Print each increase in `reader` as soon as it's read, then the total.
+pub fn stream<D: Depth>(reader: Box<dyn BufRead>, window: NonZeroUsize) -> Result<(), Box<dyn Error>> {
+    let total = d1::stream::increases::<D, _>(reader, window, |increase| {
+        println!("reading {}: increase {}", increase.index, increase.count);
+    })?;
+    println!("{}", total);
+    Ok(())
+}
+
+/// Parse the whole of `input` and print how many increases there are, a full report, or a statistic of every window.
+pub fn analyse<D: Depth>(
+    input: &[u8],
+    window: NonZeroUsize,
+    mode: Mode,
+    report: Option<ReportFormat>,
+    stat: Option<Stat>,
+    parallel: bool,
+) -> Result<(), Box<dyn Error>> {
+    let sweep = d1::parse_with::<D>(input, mode).map_err(|errors| {
+        for e in &errors {
+            eprintln!("{}", e);
+        }
+        format!("{} lines aren't depths", errors.len())
+    })?;
+    if sweep.skipped() > 0 {
+        eprintln!("skipped {} lines that aren't depths", sweep.skipped());
+    }
+    if sweep.interpolated > 0 {
+        eprintln!("interpolated {} lines that aren't depths", sweep.interpolated);
+    }
+
+    if let Some(stat) = stat {
+        for (i, depths) in sweep.segments.iter().enumerate() {
+            if sweep.segments.len() > 1 {
+                println!("stretch {}:", i + 1);
+            }
+            let depths = depths.iter().copied();
+            match stat {
+                Stat::Sum => print_each(depths.rolling_sum(window)),
+                Stat::Mean => print_each(depths.rolling_mean(window)),
+                Stat::Min => print_each(depths.rolling_min(window)),
+                Stat::Max => print_each(depths.rolling_max(window)),
+                Stat::Median => print_each(depths.rolling_median(window)),
+            }
+        }
+        return Ok(());
+    }
+    let Some(format) = report else {
+        let count = if parallel { d1::parallel::count_increases_par } else { d1::rolling::count_increases };
+        let increases: usize = sweep.segments.iter().map(|depths| count(depths, window)).sum();
+        println!("{}", increases);
+        return Ok(());
+    };
+    let reports: Vec<_> = sweep.segments.iter().map(|depths| d1::report::report(depths, window)).collect();
+    match format {
+        ReportFormat::Json => println!("{}", serde_json::to_string_pretty(&reports)?),
+        ReportFormat::Table => {
+            for (i, report) in reports.iter().enumerate() {
+                if reports.len() > 1 {
+                    println!("stretch {}:", i + 1);
+                }
+                println!("{}", report);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// One per line, `Debug` since that's all that `Depth::Sum` promises and it's the same as `Display` for numbers.
+fn print_each<T: Debug>(values: impl Iterator<Item = T>) {
+    for value in values {
+        println!("{:?}", value);
+    }
+}
@@ -0,0 +1,94 @@
+use std::{error::Error, fs, path::Path, time::Duration};
+
+use aoc::Timings;
+use serde::Serialize;
+
+use crate::days::Day;
+
+/// Summary statistics for one stage over all the runs, in nanoseconds.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Stats {
+    pub min: u64,
+    pub median: u64,
+    pub mean: u64,
+    pub max: u64,
+}
+
+impl Stats {
+    fn new(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+        let nanos = |d: Duration| d.as_nanos() as u64;
+        let total: u64 = samples.iter().copied().map(nanos).sum();
+        Stats {
+            min: nanos(samples[0]),
+            median: nanos(samples[samples.len() / 2]),
+            mean: total / samples.len() as u64,
+            max: nanos(samples[samples.len() - 1]),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Report {
+    pub day: &'static str,
+    pub runs: usize,
+    pub parse: Stats,
+    pub part_1: Stats,
+    pub part_2: Stats,
+    /// Parse plus both parts, per run.
+    pub total: Stats,
+}
+
+/// Time `day` on its bundled input `runs` times.
+pub fn time_day(day: &Day, runs: usize) -> Result<Report, Box<dyn Error>> {
+    let runs = runs.max(1);
+    let timings = (0..runs).map(|_| (day.time)(day.input)).collect::<Result<Vec<Timings>, _>>()?;
+    let stage = |f: fn(&Timings) -> Duration| Stats::new(timings.iter().map(f).collect());
+    Ok(Report {
+        day: day.name,
+        runs,
+        parse: stage(|t| t.parse),
+        part_1: stage(|t| t.part_1),
+        part_2: stage(|t| t.part_2),
+        total: stage(|t| t.parse + t.part_1 + t.part_2),
+    })
+}
+
+/// Time every one of `days`, print a table of the median times and optionally write every statistic out as JSON.
+pub fn run<'a>(days: impl IntoIterator<Item = &'a Day>, runs: usize, json: Option<&Path>) -> Result<(), Box<dyn Error>> {
+    let mut reports = vec![];
+    println!("{:<4} {:>12} {:>12} {:>12} {:>12}", "day", "parse", "part 1", "part 2", "total");
+    for day in days {
+        match time_day(day, runs) {
+            Ok(report) => {
+                println!(
+                    "{:<4} {:>12} {:>12} {:>12} {:>12}",
+                    report.day,
+                    format_nanos(report.parse.median),
+                    format_nanos(report.part_1.median),
+                    format_nanos(report.part_2.median),
+                    format_nanos(report.total.median),
+                );
+                reports.push(report);
+            }
+            Err(e) => println!("{:<4} error: {}", day.name, e),
+        }
+    }
+    println!("(median of {} runs)", runs.max(1));
+
+    if let Some(path) = json {
+        fs::write(path, serde_json::to_string_pretty(&reports)?)?;
+    }
+    Ok(())
+}
+
+fn format_nanos(nanos: u64) -> String {
+    let d = Duration::from_nanos(nanos);
+    if d >= Duration::from_secs(1) {
+        format!("{:.2}s", d.as_secs_f64())
+    } else if d >= Duration::from_millis(1) {
+        format!("{:.2}ms", d.as_secs_f64() * 1e3)
+    } else {
+        format!("{:.2}µs", d.as_secs_f64() * 1e6)
+    }
+}
@@ -0,0 +1,146 @@
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt::{self, Display},
+    panic::{self, AssertUnwindSafe},
+};
+
+use aoc::{Part, Unsolved};
+use serde::Deserialize;
+
+use crate::days::Day;
+
+const MANIFEST: &str = include_str!("../../answers.toml");
+
+/// The expected answers for one input file.
+#[derive(Debug, Default, Deserialize)]
+pub struct Expected {
+    part_1: Option<String>,
+    part_2: Option<String>,
+}
+
+/// Day name -> input name (`input` or `test`) -> expected answers.
+pub type Manifest = BTreeMap<String, BTreeMap<String, Expected>>;
+
+pub fn manifest() -> Result<Manifest, Box<dyn Error>> {
+    Ok(toml::from_str(MANIFEST)?)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Mismatch { expected: String, actual: String },
+    /// The solver returned an error or panicked.
+    Fail(String),
+    /// The part hasn't been solved yet.
+    Unsolved,
+    /// There's no expected answer in the manifest to check against.
+    Missing,
+}
+
+impl Status {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Status::Mismatch { .. } | Status::Fail(_))
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Pass => write!(f, "pass"),
+            Status::Mismatch { expected, actual } => write!(f, "MISMATCH: expected {}, got {}", expected, actual),
+            Status::Fail(cause) => write!(f, "FAIL: {}", cause),
+            Status::Unsolved => write!(f, "unsolved"),
+            Status::Missing => write!(f, "no expected answer"),
+        }
+    }
+}
+
+/// Run every part of `day` against both its bundled inputs, returning `(input name, part, status)` for each.
+pub fn verify_day(day: &Day, expected: Option<&BTreeMap<String, Expected>>) -> Vec<(&'static str, Part, Status)> {
+    let mut results = vec![];
+    for (name, input) in [("test", day.test), ("input", day.input)] {
+        let expected = expected.and_then(|e| e.get(name));
+        for part in [Part::One, Part::Two] {
+            let expected = expected.and_then(|e| match part {
+                Part::One => e.part_1.as_deref(),
+                Part::Two => e.part_2.as_deref(),
+            });
+            results.push((name, part, check(day, input, part, expected)));
+        }
+    }
+    results
+}
+
+fn check(day: &Day, input: &str, part: Part, expected: Option<&str>) -> Status {
+    // Solvers are allowed to panic on input they can't handle, that's a failure rather than a reason to stop
+    let result = panic::catch_unwind(AssertUnwindSafe(|| (day.solve)(input, Some(part))));
+    let answers = match result {
+        Ok(Ok(answers)) => answers,
+        Ok(Err(e)) => return Status::Fail(e.to_string()),
+        Err(payload) => {
+            let message = payload.downcast_ref::<&str>().map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string());
+            return Status::Fail(format!("panicked: {}", message));
+        }
+    };
+    let actual = match part {
+        Part::One => answers.part_1,
+        Part::Two => answers.part_2,
+    }.unwrap_or_default();
+
+    if actual == Unsolved.to_string() {
+        Status::Unsolved
+    } else {
+        match expected {
+            None => Status::Missing,
+            Some(expected) if expected == actual => Status::Pass,
+            Some(expected) => Status::Mismatch { expected: expected.to_string(), actual },
+        }
+    }
+}
+
+/// Verify `days` against the manifest, print a report and return the number of failures.
+pub fn run<'a>(days: impl IntoIterator<Item = &'a Day>) -> Result<usize, Box<dyn Error>> {
+    let manifest = manifest()?;
+
+    // The panics are reported in the table so don't also spray them over stderr
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let mut failures = 0;
+    for day in days {
+        for (name, part, status) in verify_day(day, manifest.get(day.name)) {
+            let part = match part {
+                Part::One => 1,
+                Part::Two => 2,
+            };
+            println!("{:<4} {:<6} part {}  {}", day.name, name, part, status);
+            if status.is_failure() {
+                failures += 1;
+            }
+        }
+    }
+    panic::set_hook(hook);
+    Ok(failures)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::days::{self, DAYS};
+
+    #[test]
+    fn manifest_covers_every_day() -> Result<(), Box<dyn Error>> {
+        let manifest = manifest()?;
+        for name in manifest.keys() {
+            assert!(days::find(name).is_some(), "manifest has answers for unknown day {}", name);
+        }
+        for day in DAYS {
+            let expected = manifest.get(day.name).ok_or(format!("no answers for day {}", day.name))?;
+            assert!(expected.contains_key("test"));
+            assert!(expected.contains_key("input"));
+        }
+        Ok(())
+    }
+}